@@ -0,0 +1,39 @@
+//! Benchmark headless do loop de frames: roda o nestest.nes sem frontend e mede frames/s.
+//!
+//! cargo bench -p nes_core --bench frame_loop
+
+use std::time::Instant;
+use nes_core::Nes;
+
+const WARMUP_FRAMES: u32 = 60;
+const FRAMES: u32 = 600;
+
+fn load_nestest() -> Nes {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../nestest.nes");
+    let contents = std::fs::read(path).expect("nestest.nes not found");
+    Nes::from_rom_bytes(&contents).expect("invalid nestest.nes")
+}
+
+fn run_frames(nes: &mut Nes, frames: u32) {
+    for _ in 0..frames {
+        assert!(nes.run_frame(), "CPU halted: {:?}", nes.halted());
+    }
+}
+
+fn main() {
+    let mut nes = load_nestest();
+
+    run_frames(&mut nes, WARMUP_FRAMES);
+
+    let start = Instant::now();
+    run_frames(&mut nes, FRAMES);
+    let elapsed = start.elapsed();
+
+    println!(
+        "frame_loop: {} frames em {:.2?} ({:.1} frames/s, {:.0} ns/ciclo de CPU)",
+        FRAMES,
+        elapsed,
+        (FRAMES as f64) / elapsed.as_secs_f64(),
+        (elapsed.as_nanos() as f64) / (nes.cycles() as f64)
+    );
+}
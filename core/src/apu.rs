@@ -0,0 +1,827 @@
+// Tabelas auxiliares baseadas no hardware do NES
+const LENGTH_TABLE: [u8; 32] = [
+    10, 254, 20, 2, 40, 4, 80, 6, 160, 8, 60, 10, 14, 12, 26, 14,
+    12, 16, 24, 18, 48, 20, 96, 22, 192, 24, 72, 26, 16, 28, 32, 30,
+];
+// Períodos do ruído em ciclos de CPU (NTSC)
+const NOISE_TIMER_TABLE: [u16; 16] = [
+    4, 8, 16, 32, 64, 96, 128, 160,
+    202, 254, 380, 508, 762, 1016, 2034, 4068,
+];
+// Períodos do DMC em ciclos de CPU (NTSC)
+const DMC_RATE_TABLE: [u16; 16] = [
+    428, 380, 340, 320, 286, 254, 226, 214, 190, 160, 142, 128, 106, 84, 72, 54,
+];
+const DUTY_TABLE: [[u8; 8]; 4] = [
+    [0, 1, 0, 0, 0, 0, 0, 0], // 12.5%
+    [0, 1, 1, 0, 0, 0, 0, 0], // 25%
+    [0, 1, 1, 1, 1, 0, 0, 0], // 50%
+    [1, 0, 0, 1, 1, 1, 1, 1], // 25% invertido
+];
+const TRIANGLE_SEQUENCE: [u8; 32] = [
+    15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0,
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+];
+
+/// Clock da CPU (NTSC) em Hz; a APU é avançada uma vez por ciclo de CPU.
+pub const CPU_CLOCK_HZ: f64 = 1_789_773.0;
+pub const DEFAULT_SAMPLE_RATE: f64 = 44_100.0;
+
+/// APU – Audio Processing Unit
+/// Agrupa os canais de áudio. Cada chamada de tick atualiza os canais.
+pub struct APU {
+    pulse1: PulseChannel,
+    pulse2: PulseChannel,
+    triangle: TriangleChannel,
+    noise: NoiseChannel,
+    dmc: DMCChannel,
+    frame_counter: u16, // Ciclos de CPU desde o início da sequência do frame sequencer
+    five_step_mode: bool, // Modo do frame sequencer (bit 7 de 0x4017)
+    irq_inhibit: bool,  // Bit 6 de 0x4017
+    frame_irq: bool,    // Flag de IRQ do frame counter (bit 6 de 0x4015)
+    cycle: u64,         // Ciclos de CPU desde o power-up
+
+    // Mixer não-linear (tabelas de lookup do NESdev wiki)
+    pulse_table: [f32; 31],
+    tnd_table: [f32; 203],
+
+    // Reamostragem do clock da CPU para a taxa de saída
+    sample_rate: f64,
+    sample_clock: f64,   // Acumulador fracionário de ciclos até a próxima amostra
+    sample_sum: f32,     // Soma das saídas do mixer desde a última amostra (filtro box)
+    sample_count: u32,
+    filter_prev_in: f32, // Estado do filtro passa-alta (remove o nível DC)
+    filter_prev_out: f32,
+    samples: Vec<f32>,
+}
+
+impl APU {
+    pub fn new() -> Self {
+        APU {
+            pulse1: PulseChannel::new(1),
+            pulse2: PulseChannel::new(2),
+            triangle: TriangleChannel::new(),
+            noise: NoiseChannel::new(),
+            dmc: DMCChannel::new(),
+            frame_counter: 0,
+            five_step_mode: false,
+            irq_inhibit: false,
+            frame_irq: false,
+            cycle: 0,
+            pulse_table: std::array::from_fn(|n| {
+                if n == 0 { 0.0 } else { 95.52 / (8128.0 / (n as f32) + 100.0) }
+            }),
+            tnd_table: std::array::from_fn(|n| {
+                if n == 0 { 0.0 } else { 163.67 / (24329.0 / (n as f32) + 100.0) }
+            }),
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            sample_clock: 0.0,
+            sample_sum: 0.0,
+            sample_count: 0,
+            filter_prev_in: 0.0,
+            filter_prev_out: 0.0,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Estado de power-up, mantendo a taxa de amostragem configurada pelo frontend.
+    pub fn power_on(&mut self) {
+        let sample_rate = self.sample_rate;
+        *self = APU::new();
+        self.sample_rate = sample_rate;
+    }
+
+    /// Sinal de reset: silencia os canais ($4015 = 0), reinicia o frame sequencer no modo
+    /// atual, zera a fase do triângulo e mantém só o bit 0 da saída do DMC.
+    pub fn reset(&mut self) {
+        self.write_register(0x4015, 0);
+        self.frame_counter = 0;
+        self.frame_irq = false;
+        self.triangle.sequence_position = 0;
+        self.dmc.output_level &= 0x01;
+    }
+
+    /// Define a taxa de amostragem da saída (ex.: `AudioContext.sampleRate`).
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        if sample_rate > 0.0 {
+            self.sample_rate = sample_rate;
+        }
+    }
+
+    /// Retorna e esvazia as amostras geradas desde a última chamada, em [-1.0, 1.0].
+    pub fn take_samples(&mut self) -> Vec<f32> {
+        std::mem::take(&mut self.samples)
+    }
+
+    /// Linha de IRQ da APU: frame counter ou fim de sample do DMC.
+    pub fn irq_pending(&self) -> bool {
+        self.frame_irq || self.dmc.irq_flag
+    }
+
+    /// Período do timer do pulse `channel` (1 ou 2), já com os ajustes do sweep.
+    pub fn pulse_period(&self, channel: u8) -> u16 {
+        if channel == 1 { self.pulse1.timer_reload } else { self.pulse2.timer_reload }
+    }
+
+    /// Endereço que o memory reader do DMC quer buscar, se o buffer de sample estiver vazio.
+    /// Quem controla o bus (ver `BUS::clock_apu`) faz a leitura e entrega via `dmc_fill`.
+    pub fn dmc_read_address(&self) -> Option<u16> {
+        self.dmc.read_address()
+    }
+
+    pub fn dmc_fill(&mut self, value: u8) {
+        self.dmc.fill(value);
+    }
+
+    /// Mixer não-linear: combina a saída dos cinco canais em um nível entre 0.0 e 1.0.
+    fn mix(&self) -> f32 {
+        let pulse = self.pulse1.output() + self.pulse2.output();
+        let tnd = 3 * self.triangle.output() + 2 * self.noise.output() + self.dmc.output();
+        self.pulse_table[pulse as usize] + self.tnd_table[tnd as usize]
+    }
+
+    /// Acumula a saída do mixer e emite uma amostra sempre que passa um período da taxa de saída.
+    fn generate_sample(&mut self) {
+        self.sample_sum += self.mix();
+        self.sample_count += 1;
+        self.sample_clock += self.sample_rate;
+
+        if self.sample_clock >= CPU_CLOCK_HZ {
+            self.sample_clock -= CPU_CLOCK_HZ;
+
+            let input = self.sample_sum / (self.sample_count as f32);
+            self.sample_sum = 0.0;
+            self.sample_count = 0;
+
+            // Passa-alta de primeira ordem para centralizar o sinal em zero
+            let output = input - self.filter_prev_in + 0.996 * self.filter_prev_out;
+            self.filter_prev_in = input;
+            self.filter_prev_out = output;
+
+            self.samples.push(output.clamp(-1.0, 1.0));
+        }
+    }
+
+    /// Tick: deve ser chamado uma vez por ciclo de CPU.
+    /// Os timers do triângulo e do DMC rodam no clock da CPU; pulse e noise a cada ciclo de APU
+    /// (metade do clock da CPU).
+    pub fn tick(&mut self) {
+        self.triangle.tick();
+        self.noise.tick();
+        self.dmc.tick();
+        if self.cycle % 2 == 1 {
+            self.pulse1.tick();
+            self.pulse2.tick();
+        }
+        self.cycle = self.cycle.wrapping_add(1);
+        self.clock_frame_sequencer();
+        self.generate_sample();
+    }
+
+    /// Frame sequencer: gera os clocks de quarter frame (envelopes e linear counter) e
+    /// half frame (length counters e sweeps) a ~240 Hz, no modo de 4 ou 5 passos.
+    /// Os valores são em ciclos de CPU (NTSC).
+    fn clock_frame_sequencer(&mut self) {
+        self.frame_counter += 1;
+        match (self.five_step_mode, self.frame_counter) {
+            (_, 7457) => self.clock_quarter_frame(),
+            (_, 14913) => {
+                self.clock_quarter_frame();
+                self.clock_half_frame();
+            }
+            (_, 22371) => self.clock_quarter_frame(),
+            (false, 29828) => self.set_frame_irq(),
+            (false, 29829) => {
+                self.clock_quarter_frame();
+                self.clock_half_frame();
+                self.set_frame_irq();
+            }
+            (false, 29830) => {
+                self.set_frame_irq();
+                self.frame_counter = 0;
+            }
+            (true, 37281) => {
+                self.clock_quarter_frame();
+                self.clock_half_frame();
+            }
+            (true, 37282) => self.frame_counter = 0,
+            _ => {}
+        }
+    }
+
+    fn clock_quarter_frame(&mut self) {
+        self.pulse1.clock_envelope();
+        self.pulse2.clock_envelope();
+        self.triangle.clock_linear_counter();
+        self.noise.clock_envelope();
+    }
+
+    fn clock_half_frame(&mut self) {
+        self.pulse1.clock_length_counter();
+        self.pulse1.clock_sweep();
+        self.pulse2.clock_length_counter();
+        self.pulse2.clock_sweep();
+        self.triangle.clock_length_counter();
+        self.noise.clock_length_counter();
+    }
+
+    fn set_frame_irq(&mut self) {
+        if !self.irq_inhibit {
+            self.frame_irq = true;
+        }
+    }
+
+    /// write_register: Simula os writes na faixa 0x4000 a 0x4017 mapeados para a APU.
+    pub fn write_register(&mut self, addr: u16, value: u8) {
+        match addr {
+            0x4000 => self.pulse1.write_control(value),
+            0x4001 => self.pulse1.write_sweep(value),
+            0x4002 => self.pulse1.write_timer_low(value),
+            0x4003 => self.pulse1.write_timer_high(value),
+            0x4004 => self.pulse2.write_control(value),
+            0x4005 => self.pulse2.write_sweep(value),
+            0x4006 => self.pulse2.write_timer_low(value),
+            0x4007 => self.pulse2.write_timer_high(value),
+            0x4008 => self.triangle.write_linear_counter(value),
+            0x400A => self.triangle.write_timer_low(value),
+            0x400B => self.triangle.write_timer_high(value),
+            0x400C => self.noise.write_control(value),
+            0x400E => self.noise.write_timer(value),
+            0x400F => self.noise.write_length_counter(value),
+            0x4010 => self.dmc.write_control(value),
+            0x4011 => self.dmc.write_output_level(value),
+            0x4012 => self.dmc.write_sample_address(value),
+            0x4013 => self.dmc.write_sample_length(value),
+            0x4015 => {
+                self.pulse1.set_enabled(value & 0x01 != 0);
+                self.pulse2.set_enabled(value & 0x02 != 0);
+                self.triangle.set_enabled(value & 0x04 != 0);
+                self.noise.set_enabled(value & 0x08 != 0);
+                self.dmc.set_enabled(value & 0x10 != 0);
+            },
+            0x4017 => {
+                // Frame counter: bit 7 = modo de 5 passos, bit 6 = inibe IRQ
+                self.five_step_mode = value & 0x80 != 0;
+                self.irq_inhibit = value & 0x40 != 0;
+                if self.irq_inhibit {
+                    self.frame_irq = false;
+                }
+                self.frame_counter = 0;
+                // No modo de 5 passos a escrita gera imediatamente um quarter e half frame
+                if self.five_step_mode {
+                    self.clock_quarter_frame();
+                    self.clock_half_frame();
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// read_register: Apenas o registrador de status (0x4015) é lido.
+    /// Bits 0-4: length counters / bytes restantes do DMC, bit 6: IRQ do frame counter,
+    /// bit 7: IRQ do DMC. A leitura limpa a flag de IRQ do frame counter.
+    pub fn read_register(&mut self, addr: u16) -> u8 {
+        match addr {
+            0x4015 => {
+                let mut status = 0;
+                if self.pulse1.length_counter > 0 { status |= 0x01; }
+                if self.pulse2.length_counter > 0 { status |= 0x02; }
+                if self.triangle.length_counter > 0 { status |= 0x04; }
+                if self.noise.length_counter > 0 { status |= 0x08; }
+                if self.dmc.bytes_remaining > 0 { status |= 0x10; }
+                if self.frame_irq { status |= 0x40; }
+                if self.dmc.irq_flag { status |= 0x80; }
+                self.frame_irq = false;
+                status
+            }
+            _ => 0,
+        }
+    }
+}
+
+// =============================
+// Envelope
+// =============================
+/// Gerador de envelope usado pelos canais pulse e noise. Recebe o clock de quarter frame.
+#[derive(Default)]
+pub struct Envelope {
+    start: bool,     // Setado por escritas no quarto registrador do canal
+    divider: u8,
+    decay_level: u8, // Volume corrente (15 decaindo até 0)
+}
+
+impl Envelope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `period` é o volume/período do registrador de controle; `looping` é a flag de loop
+    /// (que também trava o length counter).
+    pub fn clock(&mut self, period: u8, looping: bool) {
+        if self.start {
+            self.start = false;
+            self.decay_level = 15;
+            self.divider = period;
+        } else if self.divider == 0 {
+            self.divider = period;
+            if self.decay_level > 0 {
+                self.decay_level -= 1;
+            } else if looping {
+                self.decay_level = 15;
+            }
+        } else {
+            self.divider -= 1;
+        }
+    }
+}
+
+// =============================
+// Pulse Channel
+// =============================
+pub struct PulseChannel {
+    enabled: bool,
+    volume: u8,         // 0-15 (volume constante ou período do envelope)
+    duty_cycle: u8,     // 0-3: seleciona a forma de onda (usando uma tabela de duty cycle)
+    timer: u16,         // Contador que determina o período da onda
+    timer_reload: u16,  // Valor para recarregar o timer
+    length_counter: u8, // Controla a duração do som
+    envelope: Envelope, // Decaimento do volume
+    constant_volume: bool,
+    envelope_loop: bool, // Também funciona como halt do length counter
+    sequence_position: u8, // Posição (0-7) na duty table
+
+    // Sweep (0x4001 / 0x4005) – modula a frequência
+    sweep_enabled: bool,
+    sweep_period: u8,
+    sweep_negate: bool,
+    sweep_shift: u8,
+    sweep_reload: bool,
+    sweep_divider: u8,
+    ones_complement: bool, // O pulse 1 subtrai um a mais ao negar (complemento de um)
+}
+
+impl PulseChannel {
+    /// `channel` é 1 ou 2; os dois canais diferem apenas na negação do sweep.
+    pub fn new(channel: u8) -> Self {
+        PulseChannel {
+            enabled: false,
+            volume: 15,
+            duty_cycle: 0,
+            timer: 0,
+            timer_reload: 0,
+            length_counter: 0,
+            envelope: Envelope::new(),
+            constant_volume: false,
+            envelope_loop: false,
+            sequence_position: 0,
+            sweep_enabled: false,
+            sweep_period: 0,
+            sweep_negate: false,
+            sweep_shift: 0,
+            sweep_reload: false,
+            sweep_divider: 0,
+            ones_complement: channel == 1,
+        }
+    }
+
+    /// tick: Atualiza o timer e avança a posição na duty sequencia (a cada ciclo de APU).
+    pub fn tick(&mut self) {
+        if self.timer > 0 {
+            self.timer -= 1;
+        } else {
+            self.timer = self.timer_reload;
+            self.sequence_position = (self.sequence_position + 1) % 8;
+        }
+    }
+
+    // Quarter frame
+    pub fn clock_envelope(&mut self) {
+        self.envelope.clock(self.volume, self.envelope_loop);
+    }
+
+    // Half frame
+    pub fn clock_length_counter(&mut self) {
+        if !self.envelope_loop && self.length_counter > 0 {
+            self.length_counter -= 1;
+        }
+    }
+
+    // Half frame
+    pub fn clock_sweep(&mut self) {
+        if self.sweep_divider == 0 && self.sweep_enabled && self.sweep_shift > 0 && !self.is_muted() {
+            self.timer_reload = self.sweep_target();
+        }
+        if self.sweep_divider == 0 || self.sweep_reload {
+            self.sweep_divider = self.sweep_period;
+            self.sweep_reload = false;
+        } else {
+            self.sweep_divider -= 1;
+        }
+    }
+
+    /// Período alvo calculado continuamente pela unidade de sweep.
+    fn sweep_target(&self) -> u16 {
+        let change = self.timer_reload >> self.sweep_shift;
+        if self.sweep_negate {
+            let change = change + (self.ones_complement as u16);
+            self.timer_reload.saturating_sub(change)
+        } else {
+            self.timer_reload + change
+        }
+    }
+
+    /// Períodos menores que 8 ou um alvo do sweep acima de $7FF silenciam o canal.
+    fn is_muted(&self) -> bool {
+        self.timer_reload < 8 || self.sweep_target() > 0x7ff
+    }
+
+    // Escrita em 0x4000 – Controle: duty, volume/envelope e flags de envelope loop/volume constante.
+    pub fn write_control(&mut self, value: u8) {
+        self.constant_volume = value & 0x10 != 0;
+        self.volume = value & 0x0F;
+        self.envelope_loop = value & 0x20 != 0;
+        self.duty_cycle = (value >> 6) & 0x03;
+    }
+
+    // Escrita em 0x4001 – Sweep: EPPP NSSS (enable, período, negate, shift).
+    pub fn write_sweep(&mut self, value: u8) {
+        self.sweep_enabled = value & 0x80 != 0;
+        self.sweep_period = (value >> 4) & 0x07;
+        self.sweep_negate = value & 0x08 != 0;
+        self.sweep_shift = value & 0x07;
+        self.sweep_reload = true;
+    }
+
+    // Escrita em 0x4002 – Baixa parte do timer.
+    pub fn write_timer_low(&mut self, value: u8) {
+        self.timer_reload = (self.timer_reload & 0xFF00) | value as u16;
+    }
+
+    // Escrita em 0x4003 – Alta parte do timer, recarrega o length counter e reinicia o envelope.
+    pub fn write_timer_high(&mut self, value: u8) {
+        self.timer_reload = (self.timer_reload & 0x00FF) | (((value & 0x07) as u16) << 8);
+        if self.enabled {
+            self.length_counter = LENGTH_TABLE[(value >> 3) as usize];
+        }
+        self.sequence_position = 0;
+        self.envelope.start = true;
+    }
+
+    /// Nível de saída atual (0-15).
+    pub fn output(&self) -> u8 {
+        if !self.enabled || self.length_counter == 0 || self.is_muted() {
+            return 0;
+        }
+        if DUTY_TABLE[self.duty_cycle as usize][self.sequence_position as usize] == 0 {
+            return 0;
+        }
+        if self.constant_volume { self.volume } else { self.envelope.decay_level }
+    }
+    
+    // Ativa ou desativa o canal. Se desativado, zera o length counter.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.length_counter = 0;
+        }
+    }
+}
+
+// =============================
+// Triangle Channel
+// =============================
+pub struct TriangleChannel {
+    enabled: bool,
+    timer: u16,
+    timer_reload: u16,
+    sequence_position: u8, // Posiciona de 0 a 31 na sequência triangular
+    length_counter: u8,
+    linear_counter: u8,    // Valor decrescente que controla a duração da onda triangular
+    linear_reload: u8,
+    linear_control: bool,  // Também funciona como halt do length counter
+    linear_reload_flag: bool,
+}
+
+impl TriangleChannel {
+    pub fn new() -> Self {
+        TriangleChannel {
+            enabled: false,
+            timer: 0,
+            timer_reload: 0,
+            sequence_position: 0,
+            length_counter: 0,
+            linear_counter: 0,
+            linear_reload: 0,
+            linear_control: false,
+            linear_reload_flag: false,
+        }
+    }
+    
+    /// tick: Atualiza o timer (a cada ciclo de CPU) e avança a sequência triangular enquanto
+    /// o length counter e o linear counter forem diferentes de zero.
+    pub fn tick(&mut self) {
+        if self.timer > 0 {
+            self.timer -= 1;
+        } else {
+            self.timer = self.timer_reload;
+            if self.length_counter > 0 && self.linear_counter > 0 {
+                self.sequence_position = (self.sequence_position + 1) % 32;
+            }
+        }
+    }
+
+    // Quarter frame
+    pub fn clock_linear_counter(&mut self) {
+        if self.linear_reload_flag {
+            self.linear_counter = self.linear_reload;
+        } else if self.linear_counter > 0 {
+            self.linear_counter -= 1;
+        }
+        if !self.linear_control {
+            self.linear_reload_flag = false;
+        }
+    }
+
+    // Half frame
+    pub fn clock_length_counter(&mut self) {
+        if !self.linear_control && self.length_counter > 0 {
+            self.length_counter -= 1;
+        }
+    }
+
+    /// Nível de saída atual (0-15) seguindo a sequência triangular de 32 passos.
+    pub fn output(&self) -> u8 {
+        TRIANGLE_SEQUENCE[self.sequence_position as usize]
+    }
+
+    // Escrita em 0x4008 – Linear counter (parte alta)
+    pub fn write_linear_counter(&mut self, value: u8) {
+        self.linear_reload = value & 0x7F;
+        self.linear_control = value & 0x80 != 0;
+    }
+    // Escrita em 0x400A – Baixa parte do timer
+    pub fn write_timer_low(&mut self, value: u8) {
+        self.timer_reload = (self.timer_reload & 0xFF00) | value as u16;
+    }
+    // Escrita em 0x400B – Alta parte do timer, atualiza o length counter e recarrega o linear counter
+    pub fn write_timer_high(&mut self, value: u8) {
+        self.timer_reload = (self.timer_reload & 0x00FF) | (((value & 0x07) as u16) << 8);
+        if self.enabled {
+            self.length_counter = LENGTH_TABLE[(value >> 3) as usize];
+        }
+        self.linear_reload_flag = true;
+    }
+    
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.length_counter = 0;
+        }
+    }
+}
+
+// =============================
+// Noise Channel
+// =============================
+pub struct NoiseChannel {
+    enabled: bool,
+    timer: u16,
+    timer_reload: u16,
+    length_counter: u8,
+    envelope: Envelope,
+    constant_volume: bool,
+    envelope_loop: bool, // Também funciona como halt do length counter
+    volume: u8,
+    mode: bool,          // Modo "loop": feedback do bit 6 em vez do bit 1
+    shift_register: u16, // Usado para gerar ruído (LFSR de 15 bits)
+}
+
+impl NoiseChannel {
+    pub fn new() -> Self {
+        NoiseChannel {
+            enabled: false,
+            timer: 0,
+            timer_reload: 0,
+            length_counter: 0,
+            envelope: Envelope::new(),
+            constant_volume: false,
+            envelope_loop: false,
+            volume: 15,
+            mode: false,
+            shift_register: 1, // Valor inicial não pode ser 0
+        }
+    }
+    
+    pub fn tick(&mut self) {
+        if self.timer > 0 {
+            self.timer -= 1;
+        } else {
+            self.timer = self.timer_reload;
+            // Feedback: bit 0 XOR bit 1 (ou bit 6 no modo loop)
+            let tap = if self.mode { 6 } else { 1 };
+            let feedback = ((self.shift_register & 1) ^ ((self.shift_register >> tap) & 1)) & 1;
+            self.shift_register >>= 1;
+            // Insere o feedback no bit 14 (para um LFSR de 15 bits)
+            self.shift_register |= feedback << 14;
+        }
+    }
+
+    // Quarter frame
+    pub fn clock_envelope(&mut self) {
+        self.envelope.clock(self.volume, self.envelope_loop);
+    }
+
+    // Half frame
+    pub fn clock_length_counter(&mut self) {
+        if !self.envelope_loop && self.length_counter > 0 {
+            self.length_counter -= 1;
+        }
+    }
+    
+    // Escrita em 0x400C – Controle do canal Noise
+    pub fn write_control(&mut self, value: u8) {
+        self.constant_volume = value & 0x10 != 0;
+        self.envelope_loop = value & 0x20 != 0;
+        self.volume = value & 0x0F;
+    }
+    // Escrita em 0x400E – Modo (bit 7) e timer_reload usando a tabela de ruído
+    pub fn write_timer(&mut self, value: u8) {
+        self.mode = value & 0x80 != 0;
+        self.timer_reload = NOISE_TIMER_TABLE[(value & 0x0F) as usize];
+    }
+    // Escrita em 0x400F – Atualiza o length counter e reinicia o envelope
+    pub fn write_length_counter(&mut self, value: u8) {
+        if self.enabled {
+            self.length_counter = LENGTH_TABLE[(value >> 3) as usize];
+        }
+        self.envelope.start = true;
+    }
+    
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.length_counter = 0;
+        }
+    }
+
+    /// Nível de saída atual (0-15); silencia quando o bit 0 do LFSR está setado.
+    pub fn output(&self) -> u8 {
+        if !self.enabled || self.length_counter == 0 || (self.shift_register & 1) != 0 {
+            return 0;
+        }
+        if self.constant_volume { self.volume } else { self.envelope.decay_level }
+    }
+}
+
+// =============================
+// DMC Channel (Delta Modulation Channel)
+// =============================
+pub struct DMCChannel {
+    enabled: bool,
+    irq_enabled: bool,
+    loop_flag: bool,
+    timer: u16,
+    timer_reload: u16,       // Em ciclos de CPU (DMC_RATE_TABLE)
+    output_level: u8,        // 7 bits (0-127)
+
+    // Memory reader
+    sample_buffer: Option<u8>, // Byte lido da memória esperando o output unit
+    sample_address: u16,     // Endereço inicial ($4012)
+    sample_length: u16,      // Tamanho inicial ($4013)
+    current_address: u16,
+    bytes_remaining: u16,
+
+    // Output unit
+    shift_register: u8,
+    bits_remaining: u8,
+    silence: bool,
+
+    irq_flag: bool,
+}
+
+impl DMCChannel {
+    pub fn new() -> Self {
+        DMCChannel {
+            enabled: false,
+            irq_enabled: false,
+            loop_flag: false,
+            timer: 0,
+            timer_reload: DMC_RATE_TABLE[0] - 1,
+            output_level: 0,
+            sample_buffer: None,
+            sample_address: 0xC000,
+            sample_length: 1,
+            current_address: 0xC000,
+            bytes_remaining: 0,
+            shift_register: 0,
+            bits_remaining: 8,
+            silence: true,
+            irq_flag: false,
+        }
+    }
+    
+    /// tick: chamado a cada ciclo de CPU. Quando o timer zera, o output unit consome um bit
+    /// do shift register e ajusta o nível de saída em ±2.
+    pub fn tick(&mut self) {
+        if self.timer > 0 {
+            self.timer -= 1;
+            return;
+        }
+        self.timer = self.timer_reload;
+
+        if !self.silence {
+            if self.shift_register & 1 != 0 {
+                if self.output_level <= 125 {
+                    self.output_level += 2;
+                }
+            } else if self.output_level >= 2 {
+                self.output_level -= 2;
+            }
+        }
+        self.shift_register >>= 1;
+
+        self.bits_remaining -= 1;
+        if self.bits_remaining == 0 {
+            // Início de um novo ciclo de saída: esvazia o buffer no shift register
+            self.bits_remaining = 8;
+            match self.sample_buffer.take() {
+                Some(sample) => {
+                    self.silence = false;
+                    self.shift_register = sample;
+                }
+                None => self.silence = true,
+            }
+        }
+    }
+
+    /// Endereço do próximo byte do sample, quando o buffer está vazio e ainda há bytes.
+    pub fn read_address(&self) -> Option<u16> {
+        if self.sample_buffer.is_none() && self.bytes_remaining > 0 {
+            Some(self.current_address)
+        } else {
+            None
+        }
+    }
+
+    /// Recebe o byte buscado pelo memory reader e avança o endereço/contador do sample.
+    pub fn fill(&mut self, value: u8) {
+        self.sample_buffer = Some(value);
+        // O endereço dá a volta de $FFFF para $8000
+        self.current_address = if self.current_address == 0xFFFF {
+            0x8000
+        } else {
+            self.current_address + 1
+        };
+        self.bytes_remaining -= 1;
+        if self.bytes_remaining == 0 {
+            if self.loop_flag {
+                self.restart();
+            } else if self.irq_enabled {
+                self.irq_flag = true;
+            }
+        }
+    }
+
+    fn restart(&mut self) {
+        self.current_address = self.sample_address;
+        self.bytes_remaining = self.sample_length;
+    }
+
+    /// Nível de saída atual (0-127).
+    pub fn output(&self) -> u8 {
+        self.output_level
+    }
+
+    // Escrita em 0x4010 – IL-- RRRR: IRQ enable, loop e índice da tabela de taxas.
+    pub fn write_control(&mut self, value: u8) {
+        self.irq_enabled = value & 0x80 != 0;
+        self.loop_flag = value & 0x40 != 0;
+        self.timer_reload = DMC_RATE_TABLE[(value & 0x0F) as usize] - 1;
+        if !self.irq_enabled {
+            self.irq_flag = false;
+        }
+    }
+    // Escrita em 0x4011 – Define output level diretamente
+    pub fn write_output_level(&mut self, value: u8) {
+        self.output_level = value & 0x7F;
+    }
+    // Escrita em 0x4012 – Define o endereço base para samples (0xC000 | (value << 6))
+    pub fn write_sample_address(&mut self, value: u8) {
+        self.sample_address = 0xC000 | ((value as u16) << 6);
+    }
+    // Escrita em 0x4013 – Define a quantidade de bytes do sample (length)
+    pub fn write_sample_length(&mut self, value: u8) {
+        self.sample_length = ((value as u16) << 4) | 1;
+    }
+    
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        // Escrita em 0x4015 sempre limpa a flag de IRQ do DMC
+        self.irq_flag = false;
+        if !enabled {
+            self.bytes_remaining = 0;
+        } else if self.bytes_remaining == 0 {
+            self.restart();
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{ apu::APU, input::Controller, ppu::PPU, rom::ROM };
+
+pub struct BUS {
+    pub ppu: PPU,
+    ram: [u8; 2048],
+    pub rom: ROM,
+    pub apu: APU,
+    pub controllers: [Controller; 2],
+    nmi_line: bool,    // Último nível visto da linha de NMI da PPU
+    nmi_pending: bool, // Borda de subida detectada, aguardando a CPU
+    oam_dma: bool,     // Escrita em $4014 aguardando a CPU contabilizar o stall
+}
+
+impl BUS {
+    pub fn new(ppu: PPU, rom: ROM, apu: APU) -> Self {
+        BUS {
+            ppu,
+            ram: [0; 2048],
+            rom,
+            apu,
+            controllers: [Controller::new(), Controller::new()],
+            nmi_line: false,
+            nmi_pending: false,
+            oam_dma: false,
+        }
+    }
+
+    /// Liga o console: RAM, PPU, APU e controles no estado de power-up. O cartucho é mantido.
+    pub fn power_on(&mut self) {
+        self.ram = [0; 2048];
+        self.ppu = PPU::power_up(self.rom.shared_mapper());
+        self.apu.power_on();
+        self.controllers = [Controller::new(), Controller::new()];
+        self.clear_lines();
+    }
+
+    /// Botão de reset: a RAM é preservada; PPU e APU recebem o sinal de reset.
+    pub fn reset(&mut self) {
+        self.ppu.reset();
+        self.apu.reset();
+        self.clear_lines();
+    }
+
+    fn clear_lines(&mut self) {
+        self.nmi_line = false;
+        self.nmi_pending = false;
+        self.oam_dma = false;
+    }
+
+    /// Avança a PPU um ciclo e detecta a borda de subida da linha de NMI.
+    /// Retorna true quando um frame foi completado.
+    pub fn step_ppu(&mut self) -> bool {
+        let result = self.ppu.step();
+        let line = self.ppu.nmi_line();
+        if line && !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = line;
+        result.new_frame
+    }
+
+    /// Consome o NMI pendente (edge-triggered: é atendido uma única vez por borda).
+    pub fn take_nmi(&mut self) -> bool {
+        std::mem::take(&mut self.nmi_pending)
+    }
+
+    /// Indica (uma vez) que houve um OAM DMA, para a CPU descontar os ciclos parada.
+    pub fn take_oam_dma(&mut self) -> bool {
+        std::mem::take(&mut self.oam_dma)
+    }
+
+    /// Linha de IRQ (level-triggered): fica ativa enquanto alguma fonte estiver sinalizando.
+    pub fn irq_line(&self) -> bool {
+        self.apu.irq_pending() || self.rom.irq_pending()
+    }
+
+    /// Avança a APU um ciclo de CPU e atende o memory reader do DMC.
+    /// Retorna quantos ciclos a CPU fica parada por causa da leitura (DMA do DMC).
+    pub fn clock_apu(&mut self) -> u32 {
+        self.apu.tick();
+        match self.apu.dmc_read_address() {
+            Some(addr) => {
+                let value = self.read(addr);
+                self.apu.dmc_fill(value);
+                4
+            }
+            None => 0,
+        }
+    }
+
+    pub fn read(&mut self, addr: u16) -> u8 {
+        match addr {
+            0x0000..=0x1FFF => self.ram[addr as usize & 0x07FF],
+            // Os 8 registradores da PPU se repetem a cada 8 bytes até $3FFF
+            0x2000..=0x3FFF => self.ppu.read_register(0x2000 | (addr & 0x7)),
+            0x4015 => self.apu.read_register(addr),
+            0x4016 => self.controllers[0].read(),
+            0x4017 => self.controllers[1].read(),
+            // Mapper handles expansion registers, PRG-RAM and the memory mapping
+            0x4020..=0xFFFF => self.rom.read(addr),
+            _ => 0
+        }
+    }
+
+    /// Os 2 KB de RAM interna ($0000-$07FF).
+    pub fn ram(&self) -> &[u8] {
+        &self.ram
+    }
+
+    /// Leitura sem efeitos colaterais (para disassembler/debugger): registradores de
+    /// PPU, APU e controles retornam 0 em vez de serem lidos.
+    pub fn peek(&self, addr: u16) -> u8 {
+        match addr {
+            0x0000..=0x1FFF => self.ram[addr as usize & 0x07FF],
+            0x6000..=0xFFFF => self.rom.peek(addr),
+            _ => 0
+        }
+    }
+
+    pub fn write(&mut self, addr: u16, value: u8) {
+        match addr {
+            0x0000..=0x1FFF => self.ram[addr as usize & 0x07FF] = value,
+            0x2000..=0x3FFF => {
+                let register = 0x2000 | (addr & 0x7);
+                self.ppu.write_register(register, value);
+                self.rom.ppu_register_write(register, value);
+            },
+            0x4000..=0x4013 | 0x4015 | 0x4017 => self.apu.write_register(addr, value),
+            0x4014 => {
+                // DMA transfer to PPU OAM
+                let base = (value as u16) << 8;
+                for i in 0..256 {
+                    let data = self.read(base + i);
+                    self.ppu.write_register(0x2004, data);
+                }
+                self.oam_dma = true;
+            },
+            0x4016 => {
+                // O strobe é ligado aos dois controles
+                self.controllers[0].write(value);
+                self.controllers[1].write(value);
+            },
+            // Mapper handles expansion registers, PRG-RAM and bank switching
+            0x4020..=0xFFFF => self.rom.write(addr, value),
+            _ => { /* Ignore writes to other addresses */ }
+        }
+    }
+}
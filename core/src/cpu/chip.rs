@@ -1,5 +1,5 @@
-use std::collections::HashMap;
 use crate::bus::BUS;
+use crate::logging::{ Category, Level };
 
 // Flags 0b NV1B DIZC
 const CARRY: u8 = 0b0000_0001;
@@ -49,17 +49,58 @@ pub struct Registers {
     pub program_counter: u16, //PC
 }
 
+/// Qual CPU da família 6502 está sendo emulada.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CpuVariant {
+    /// 2A03/2A07 do NES: a flag D existe mas ADC/SBC ignoram o modo decimal
+    Ricoh2A03,
+    /// 6502 NMOS com modo decimal (N, V e Z seguem as quirks do NMOS)
+    Nmos6502,
+    /// Subconjunto do 65C02: modo decimal com flags válidas (+1 ciclo), D limpa em
+    /// interrupções e JMP ($xxFF) sem o bug de página
+    Cmos65C02,
+}
+
+impl CpuVariant {
+    pub fn from_name(name: &str) -> Option<CpuVariant> {
+        match name {
+            "2A03" => Some(CpuVariant::Ricoh2A03),
+            "6502" => Some(CpuVariant::Nmos6502),
+            "65C02" => Some(CpuVariant::Cmos65C02),
+            _ => None,
+        }
+    }
+}
+
+/// O que fazer com opcodes instáveis (XAA, LXA, SHA, ...) e com os JAM/KIL.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IllegalOpcodePolicy {
+    /// Emula o comportamento mais comum do 2A03; JAM trava a CPU como no hardware
+    Emulate,
+    /// Para a CPU e reporta o opcode (ver `CPU::halted`)
+    Halt,
+    /// Loga e trata o opcode como NOP
+    Log,
+}
+
 type Opcode = u8;
 type Instruction = fn(&mut CPU, AddressingModes);
-type OpcodeFunction = (Instruction, AddressingModes);
-type InstructionMap = HashMap<Opcode, OpcodeFunction>; // Opcode, Instrution
 
 pub struct CPU {
     pub registers: Registers,
-    instructions: InstructionMap,
     pub bus: BUS,
     // Campo para controle dos ciclos da instrução atual
     pub remaining_cycles: u32,
+    // Ciclos extras da instrução atual (página cruzada, branch tomado, decimal no 65C02)
+    penalty_cycles: u32,
+    // Total de ciclos de CPU desde o power-up
+    pub cycles: u64,
+    pub variant: CpuVariant,
+    pub illegal_opcode_policy: IllegalOpcodePolicy,
+    // (endereço, opcode) que parou a CPU
+    halted: Option<(u16, Opcode)>,
+    // Flag I vista pelo polling de interrupção no fim da última instrução
+    irq_masked: bool,
 }
 
 impl CPU {
@@ -73,9 +114,14 @@ impl CPU {
                 status_register: UNUSED,
                 program_counter: 0,
             },
-            instructions: HashMap::new(),
             bus,
             remaining_cycles: 0,
+            penalty_cycles: 0,
+            cycles: 0,
+            irq_masked: true,
+            variant: CpuVariant::Ricoh2A03,
+            illegal_opcode_policy: IllegalOpcodePolicy::Emulate,
+            halted: None,
         }
     }
 
@@ -85,89 +131,92 @@ impl CPU {
         opcode
     }
 
-    fn decode(&mut self) -> Option<OpcodeFunction> {
+    fn decode(&mut self) -> (Opcode, &'static OpcodeInfo) {
         let opcode: u8 = self.fetch();
-
-        if self.instructions.is_empty() {
-            self.map_instructions();
-        }
-
-        println!("Fetched Opcode: 0x{:X} - PC: {}", opcode, self.registers.program_counter);
-        web_sys::console::log_1(
-            &format!(
-                "Fetched Opcode: 0x{:X} - PC: {}",
-                opcode,
-                self.registers.program_counter
-            ).into()
-        );
-        self.instructions.get(&opcode).copied()
+        (opcode, &OPCODES[opcode as usize])
     }
 
     fn execute(&mut self) {
-        let op_function: Option<OpcodeFunction> = self.decode();
-        if let Some((func, mode)) = op_function {
-            // Log para debug
-            web_sys::console::log_1(
-                &format!(
-                    "Executing instruction at PC: {:04X}, A: {:02X}, X: {:02X}, Y: {:02X}",
-                    self.registers.program_counter,
-                    self.registers.acc,
-                    self.registers.index_x,
-                    self.registers.index_y
-                ).into()
-            );
-
-            func(self, mode);
-        } else {
-            println!("Opcode not found");
+        let interrupt_disable = (self.registers.status_register & INTERRUPT_DISABLE) != 0;
+        let (opcode, info) = self.decode();
+        self.penalty_cycles = 0;
+        (info.function)(self, info.mode);
+        self.remaining_cycles += info.cycles as u32 + self.penalty_cycles;
+        // OAM DMA ($4014) para a CPU por 513 ciclos, +1 se começar em ciclo ímpar
+        if self.bus.take_oam_dma() {
+            self.remaining_cycles += 513 + (self.cycles % 2) as u32;
         }
+
+        // CLI, SEI e PLP alteram a flag I depois do polling de interrupção, então a
+        // próxima fronteira ainda usa o valor antigo. RTI tem efeito imediato.
+        self.irq_masked = match opcode {
+            0x58 | 0x78 | 0x28 => interrupt_disable,
+            _ => (self.registers.status_register & INTERRUPT_DISABLE) != 0,
+        };
     }
 
-    pub fn reset(&mut self) {
+    /// Liga o console: registros zerados e S = $00, que a sequência de reset leva a $FD.
+    pub fn power_on(&mut self) {
         self.registers.acc = 0;
         self.registers.index_x = 0;
         self.registers.index_y = 0;
-        self.registers.stack_pointer = 0xfd;
-        self.registers.status_register = UNUSED | INTERRUPT_DISABLE;
+        self.registers.stack_pointer = 0;
+        self.registers.status_register = UNUSED;
+        self.reset();
+    }
+
+    /// Botão de reset: A, X e Y ficam como estavam, S desce 3 (os pushes de uma
+    /// interrupção, sem escrita) e a flag I é ligada.
+    pub fn reset(&mut self) {
+        self.registers.stack_pointer = self.registers.stack_pointer.wrapping_sub(3);
+        self.registers.status_register |= INTERRUPT_DISABLE;
+        self.irq_masked = true;
 
         // Lê o endereço de reset do vetor em 0xFFFC
         let reset_vector = self.read_u16(0xfffc);
-        // web_sys::console::log_1(
-        //     &format!(
-        //         "Reset vector: {:04X}, ROM data at vector: {:02X} {:02X}",
-        //         reset_vector,
-        //         self.read(reset_vector),
-        //         self.read(reset_vector + 1)
-        //     ).into()
-        // );
+        log!(
+            Category::Cpu,
+            Level::Debug,
+            "Reset vector: {:04X}, ROM data at vector: {:02X} {:02X}",
+            reset_vector,
+            self.bus.peek(reset_vector),
+            self.bus.peek(reset_vector.wrapping_add(1))
+        );
 
         self.registers.program_counter = reset_vector;
-        self.remaining_cycles = 8;
+        self.halted = None;
+
+        // A sequência de reset leva 7 ciclos
+        self.remaining_cycles = 7;
+        self.cycles = 0;
     }
 
     pub fn clock(&mut self) -> bool {
         let mut frame_complete = false;
 
-        // Se não há ciclos pendentes, busca e executa uma nova instrução
-        if self.remaining_cycles == 0 {
-            // No need to store pc_before since it's not being used
-            self.execute();
+        // Fronteira de instrução: NMI tem prioridade, depois IRQ, senão executa a próxima instrução
+        if self.remaining_cycles == 0 && self.halted.is_none() {
+            if self.bus.take_nmi() {
+                self.trigger_nmi();
+            } else if self.bus.irq_line() && !self.irq_masked {
+                self.trigger_irq();
+            } else {
+                self.execute();
+            }
         }
 
         // Consome um ciclo
         if self.remaining_cycles > 0 {
             self.remaining_cycles -= 1;
         }
+        self.cycles += 1;
 
-        // Avança a PPU (3:1 ratio)
-        for _ in 0..3 {
-            let step_result = self.bus.ppu.step();
-
-            if step_result.vblank_nmi {
-                self.trigger_nmi();
-            }
+        // A APU roda no clock da CPU; as leituras do DMC roubam ciclos da CPU
+        self.remaining_cycles += self.bus.clock_apu();
 
-            if step_result.new_frame {
+        // Avança a PPU (3:1 ratio); o NMI é só registrado e atendido na próxima fronteira
+        for _ in 0..3 {
+            if self.bus.step_ppu() {
                 frame_complete = true;
             }
         }
@@ -219,6 +268,7 @@ impl CPU {
                 let high: u8 = self.fetch();
                 let address: u16 = self.turn_in_u16(low, high);
                 let indexed_address: u16 = address.wrapping_add(self.registers.index_x as u16);
+                self.check_page_cross(address, indexed_address);
 
                 self.read(indexed_address)
             }
@@ -227,6 +277,7 @@ impl CPU {
                 let high: u8 = self.fetch();
                 let address: u16 = self.turn_in_u16(low, high);
                 let indexed_address: u16 = address.wrapping_add(self.registers.index_y as u16);
+                self.check_page_cross(address, indexed_address);
 
                 self.read(indexed_address)
             }
@@ -247,6 +298,7 @@ impl CPU {
                 let indexed_address: u16 = indirect_address.wrapping_add(
                     self.registers.index_y as u16
                 );
+                self.check_page_cross(indirect_address, indexed_address);
 
                 self.read(indexed_address)
             }
@@ -332,8 +384,9 @@ impl CPU {
                 let high = self.fetch();
                 let addr = self.turn_in_u16(low, high);
                 // Simula o bug de hardware do 6502 quando o endereço está em limite de página
+                // (corrigido no 65C02)
                 let low_addr = self.read(addr);
-                let high_addr = if (addr & 0x00ff) == 0x00ff {
+                let high_addr = if (addr & 0x00ff) == 0x00ff && self.variant != CpuVariant::Cmos65C02 {
                     self.read(addr & 0xff00)
                 } else {
                     self.read(addr + 1)
@@ -349,11 +402,21 @@ impl CPU {
         }
     }
 
+    // Leituras indexadas que cruzam página gastam um ciclo a mais
+    fn check_page_cross(&mut self, base: u16, address: u16) {
+        if (base & 0xff00) != (address & 0xff00) {
+            self.penalty_cycles += 1;
+        }
+    }
+
     fn branch_if(&mut self, condition: bool) {
         let offset = self.fetch() as i8;
         if condition {
             let old_pc = self.registers.program_counter;
             self.registers.program_counter = old_pc.wrapping_add(offset as u16);
+            // Branch tomado: +1 ciclo, +1 se o destino está em outra página
+            self.penalty_cycles += 1;
+            self.check_page_cross(old_pc, self.registers.program_counter);
         }
     }
 
@@ -446,30 +509,84 @@ impl CPU {
         // Push do PC
         self.push_u16(self.registers.program_counter);
         // Push do status (limpando o bit Break para consistência)
-        let status = self.registers.status_register & !BREAK;
+        let status = (self.registers.status_register & !BREAK) | UNUSED;
         self.push(status);
         // Carrega o vetor NMI (endereço 0xfffa)
         self.registers.program_counter = self.read_u16(0xfffa);
         // Desabilita interruptos
         self.registers.status_register |= INTERRUPT_DISABLE;
+        self.clear_decimal_on_interrupt();
+        self.irq_masked = true;
+        self.remaining_cycles += 7;
     }
 
-    // Nova função para disparar IRQ, se não estiver desabilitado
+    // Dispara um IRQ; quem chama (clock) já verificou a flag I no polling
     pub fn trigger_irq(&mut self) {
-        // IRQ só é executado se o flag de interrupção não estiver desabilitado
-        if (self.registers.status_register & INTERRUPT_DISABLE) == 0 {
-            // Push do PC
-            self.push_u16(self.registers.program_counter);
-            // Push do status (limpando o bit Break)
-            let status = self.registers.status_register & !BREAK;
-            self.push(status);
-            // Carrega o vetor IRQ (endereço 0xfffe)
-            self.registers.program_counter = self.read_u16(0xfffe);
-            // Desabilita interruptos
-            self.registers.status_register |= INTERRUPT_DISABLE;
+        // Push do PC
+        self.push_u16(self.registers.program_counter);
+        // Push do status (limpando o bit Break)
+        let status = (self.registers.status_register & !BREAK) | UNUSED;
+        self.push(status);
+        // Carrega o vetor IRQ (endereço 0xfffe)
+        self.registers.program_counter = self.read_u16(0xfffe);
+        // Desabilita interruptos
+        self.registers.status_register |= INTERRUPT_DISABLE;
+        self.clear_decimal_on_interrupt();
+        self.irq_masked = true;
+        self.remaining_cycles += 7;
+    }
+
+    // O 65C02 limpa a flag D ao entrar em uma interrupção; o NMOS não
+    fn clear_decimal_on_interrupt(&mut self) {
+        if self.variant == CpuVariant::Cmos65C02 {
+            self.registers.status_register &= !DECIMAL;
+        }
+    }
+
+    /// Endereço e opcode que travaram a CPU (JAM ou opcode instável com a política `Halt`).
+    pub fn halted(&self) -> Option<(u16, u8)> {
+        self.halted
+    }
+
+    /// Aplica a política para um opcode instável. Retorna true se ele deve ser emulado;
+    /// caso contrário a CPU já foi parada ou o operando foi pulado.
+    fn unstable_opcode(&mut self, name: &str, mode: AddressingModes) -> bool {
+        let address = self.registers.program_counter.wrapping_sub(1);
+        match self.illegal_opcode_policy {
+            IllegalOpcodePolicy::Emulate => true,
+            IllegalOpcodePolicy::Halt => {
+                self.halt_at(address);
+                false
+            }
+            IllegalOpcodePolicy::Log => {
+                log!(
+                    Category::Cpu,
+                    Level::Warn,
+                    "Unstable opcode {} at {:04X} treated as NOP",
+                    name,
+                    address
+                );
+                self.get_operand_address(mode);
+                false
+            }
         }
     }
 
+    fn jam_opcode(&mut self) {
+        let address = self.registers.program_counter.wrapping_sub(1);
+        if self.illegal_opcode_policy == IllegalOpcodePolicy::Log {
+            log!(Category::Cpu, Level::Warn, "JAM opcode at {:04X} treated as NOP", address);
+        } else {
+            self.halt_at(address);
+        }
+    }
+
+    fn halt_at(&mut self, address: u16) {
+        let opcode = self.bus.peek(address);
+        self.registers.program_counter = address;
+        self.halted = Some((address, opcode));
+    }
+
     pub fn get_all_registers(&self) -> (u8, u8, u8, u16, u8, u8) {
         (
             self.registers.acc,
@@ -0,0 +1,40 @@
+impl CPU {
+    /// Desmonta a instrução em `address` usando a tabela OPCODES.
+    /// Retorna o texto (ex.: "LDA $0200,X") e o tamanho da instrução em bytes.
+    /// Usa `BUS::peek`, então não dispara efeitos colaterais de leitura.
+    pub fn disassemble(&self, address: u16) -> (String, u8) {
+        let opcode = self.bus.peek(address);
+        let info = &OPCODES[opcode as usize];
+        let low = self.bus.peek(address.wrapping_add(1));
+        let high = self.bus.peek(address.wrapping_add(2));
+        let word = self.turn_in_u16(low, high);
+
+        let operand = match info.mode {
+            AddressingModes::Implicit => String::new(),
+            AddressingModes::Accumulator => "A".to_string(),
+            AddressingModes::Immediate => format!("#${:02X}", low),
+            AddressingModes::ZeroPage => format!("${:02X}", low),
+            AddressingModes::ZeroPageIndexedX => format!("${:02X},X", low),
+            AddressingModes::ZeroPageIndexedY => format!("${:02X},Y", low),
+            AddressingModes::Absolute => format!("${:04X}", word),
+            AddressingModes::AbsoluteIndexedX => format!("${:04X},X", word),
+            AddressingModes::AbsoluteIndexedY => format!("${:04X},Y", word),
+            AddressingModes::IndexedIndirect => format!("(${:02X},X)", low),
+            AddressingModes::IndirectIndexed => format!("(${:02X}),Y", low),
+            AddressingModes::Indirect => format!("(${:04X})", word),
+            AddressingModes::Relative => {
+                // Mostra o destino do branch, não o offset
+                let target = address.wrapping_add(2).wrapping_add(low as i8 as u16);
+                format!("${:04X}", target)
+            }
+        };
+
+        let prefix = if info.official { "" } else { "*" };
+        let text = if operand.is_empty() {
+            format!("{}{}", prefix, info.mnemonic)
+        } else {
+            format!("{}{} {}", prefix, info.mnemonic, operand)
+        };
+        (text, info.bytes)
+    }
+}
@@ -4,94 +4,33 @@ impl CPU {
         let value_for_acc: u8 = self.execute_mode(mode);
         self.registers.acc = value_for_acc;
         self.update_flags(&[FlagUpdate::Zero(value_for_acc), FlagUpdate::Negative(value_for_acc)]);
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 4, // +1 se cruzar página
-            AddressingModes::AbsoluteIndexedY => 4, // +1 se cruzar página
-            AddressingModes::IndexedIndirect => 6,
-            AddressingModes::IndirectIndexed => 5, // +1 se cruzar página
-            _ => 2,
-        };
     }
 
     fn sta(&mut self, mode: AddressingModes) {
         let address = self.get_operand_address(mode);
         self.write(address, self.registers.acc);
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 5,
-            AddressingModes::AbsoluteIndexedY => 5,
-            AddressingModes::IndexedIndirect => 6,
-            AddressingModes::IndirectIndexed => 6,
-            _ => 2,
-        };
     }
 
     fn ldx(&mut self, mode: AddressingModes) {
         let value_for_x: u8 = self.execute_mode(mode);
         self.registers.index_x = value_for_x;
         self.update_flags(&[FlagUpdate::Zero(value_for_x), FlagUpdate::Negative(value_for_x)]);
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedY => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedY => 4, // +1 se cruzar página
-            _ => 2,
-        };
     }
 
     fn stx(&mut self, mode: AddressingModes) {
         let address = self.get_operand_address(mode);
         self.write(address, self.registers.index_x);
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedY => 4,
-            AddressingModes::Absolute => 4,
-            _ => 2,
-        };
     }
 
     fn ldy(&mut self, mode: AddressingModes) {
         let value_for_y: u8 = self.execute_mode(mode);
         self.registers.index_y = value_for_y;
         self.update_flags(&[FlagUpdate::Zero(value_for_y), FlagUpdate::Negative(value_for_y)]);
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 4, // +1 se cruzar página
-            _ => 2,
-        };
     }
 
     fn sty(&mut self, mode: AddressingModes) {
         let address = self.get_operand_address(mode);
         self.write(address, self.registers.index_y);
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            _ => 2,
-        };
     }
 
     // Transfer
@@ -103,9 +42,6 @@ impl CPU {
                 FlagUpdate::Negative(self.registers.index_x),
             ]
         );
-        
-        // Adicionar ciclos (constante para TAX)
-        self.remaining_cycles += 2;
     }
 
     fn txa(&mut self, _mode: AddressingModes) {
@@ -113,9 +49,6 @@ impl CPU {
         self.update_flags(
             &[FlagUpdate::Zero(self.registers.acc), FlagUpdate::Negative(self.registers.acc)]
         );
-        
-        // Adicionar ciclos (constante para TXA)
-        self.remaining_cycles += 2;
     }
 
     fn tay(&mut self, _mode: AddressingModes) {
@@ -126,9 +59,6 @@ impl CPU {
                 FlagUpdate::Negative(self.registers.index_y),
             ]
         );
-        
-        // Adicionar ciclos (constante para TAY)
-        self.remaining_cycles += 2;
     }
 
     fn tya(&mut self, _mode: AddressingModes) {
@@ -136,49 +66,121 @@ impl CPU {
         self.update_flags(
             &[FlagUpdate::Zero(self.registers.acc), FlagUpdate::Negative(self.registers.acc)]
         );
-        
-        // Adicionar ciclos (constante para TYA)
-        self.remaining_cycles += 2;
     }
 
     // Arithmetic
     fn adc(&mut self, mode: AddressingModes) {
         let value = self.execute_mode(mode);
-        let carry = if (self.registers.status_register & CARRY) != 0 { 1 } else { 0 };
-        let result = (self.registers.acc as u16) + (value as u16) + (carry as u16);
-        let overflow =
-            (!(self.registers.acc ^ value) & (self.registers.acc ^ (result as u8)) & 0x80) != 0;
-        self.registers.acc = result as u8;
+        self.adc_value(value);
+    }
+
+    fn sbc(&mut self, mode: AddressingModes) {
+        let value = self.execute_mode(mode);
+        self.sbc_value(value);
+    }
+
+    // ADC/SBC respeitam a flag D só nas variantes com modo decimal (ver CpuVariant)
+    fn decimal_mode(&self) -> bool {
+        self.variant != CpuVariant::Ricoh2A03 && (self.registers.status_register & DECIMAL) != 0
+    }
+
+    // Usado por ADC e RRA
+    fn adc_value(&mut self, value: u8) {
+        if self.decimal_mode() {
+            self.decimal_add(value);
+        } else {
+            self.add_with_carry(value);
+        }
+    }
+
+    // Usado por SBC e ISC
+    fn sbc_value(&mut self, value: u8) {
+        if self.decimal_mode() {
+            self.decimal_subtract(value);
+        } else {
+            // A - M - (1 - C) == A + !M + C
+            self.add_with_carry(!value);
+        }
+    }
+
+    // ADC em BCD. No NMOS, N e V saem do resultado intermediário (antes do ajuste do
+    // nibble alto) e Z do resultado binário; no 65C02 N e Z refletem o resultado final.
+    fn decimal_add(&mut self, value: u8) {
+        let acc = self.registers.acc;
+        let carry = (self.registers.status_register & CARRY) as u16;
+        let binary = (acc as u16 + value as u16 + carry) as u8;
+
+        let mut low = (acc & 0x0f) as u16 + (value & 0x0f) as u16 + carry;
+        if low >= 0x0a {
+            low = ((low + 0x06) & 0x0f) + 0x10;
+        }
+        let mut sum = (acc & 0xf0) as u16 + (value & 0xf0) as u16 + low;
+        let intermediate = sum as u8;
+        let overflow = (!(acc ^ value) & (acc ^ intermediate) & 0x80) != 0;
+        if sum >= 0xa0 {
+            sum += 0x60;
+        }
+        self.registers.acc = sum as u8;
+
+        let (zero, negative) = if self.variant == CpuVariant::Cmos65C02 {
+            self.penalty_cycles += 1;
+            (self.registers.acc, self.registers.acc)
+        } else {
+            (binary, intermediate)
+        };
         self.update_flags(
             &[
-                FlagUpdate::Zero(self.registers.acc),
-                FlagUpdate::Negative(self.registers.acc),
-                FlagUpdate::Carry(result > 0xff),
+                FlagUpdate::Zero(zero),
+                FlagUpdate::Negative(negative),
+                FlagUpdate::Carry(sum >= 0x100),
                 FlagUpdate::Overflow(overflow),
             ]
         );
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 4, // +1 se cruzar página
-            AddressingModes::AbsoluteIndexedY => 4, // +1 se cruzar página
-            AddressingModes::IndexedIndirect => 6,
-            AddressingModes::IndirectIndexed => 5, // +1 se cruzar página
-            _ => 2,
+    }
+
+    // SBC em BCD. C e V são os do resultado binário nas duas variantes; N e Z também
+    // no NMOS, enquanto o 65C02 usa o resultado decimal.
+    fn decimal_subtract(&mut self, value: u8) {
+        let acc = self.registers.acc;
+        let borrow = 1 - (self.registers.status_register & CARRY) as i16;
+
+        let result = if self.variant == CpuVariant::Cmos65C02 {
+            let low = (acc & 0x0f) as i16 - (value & 0x0f) as i16 - borrow;
+            let mut result = acc as i16 - value as i16 - borrow;
+            if result < 0 {
+                result -= 0x60;
+            }
+            if low < 0 {
+                result -= 0x06;
+            }
+            result as u8
+        } else {
+            let mut low = (acc & 0x0f) as i16 - (value & 0x0f) as i16 - borrow;
+            if low < 0 {
+                low = ((low - 0x06) & 0x0f) - 0x10;
+            }
+            let mut result = (acc & 0xf0) as i16 - (value & 0xf0) as i16 + low;
+            if result < 0 {
+                result -= 0x60;
+            }
+            result as u8
         };
+
+        // Flags do cálculo binário
+        self.add_with_carry(!value);
+        self.registers.acc = result;
+        if self.variant == CpuVariant::Cmos65C02 {
+            self.penalty_cycles += 1;
+            self.update_flags(&[FlagUpdate::Zero(result), FlagUpdate::Negative(result)]);
+        }
     }
 
-    fn sbc(&mut self, mode: AddressingModes) {
-        let value = self.execute_mode(mode);
-        let carry = if (self.registers.status_register & CARRY) != 0 { 0 } else { 1 };
-        let value_comp = !value;
-        let result = (self.registers.acc as u16) + (value_comp as u16) + ((1 - carry) as u16);
+    // Soma binária usada por ADC, SBC e pelas combinações não oficiais (RRA, ISC)
+    fn add_with_carry(&mut self, value: u8) {
+        let carry = if (self.registers.status_register & CARRY) != 0 { 1 } else { 0 };
+        let result = (self.registers.acc as u16) + (value as u16) + (carry as u16);
         let overflow =
-            ((self.registers.acc ^ value) & (self.registers.acc ^ (result as u8)) & 0x80) != 0;
+            (!(self.registers.acc ^ value) & (self.registers.acc ^ (result as u8)) & 0x80) != 0;
         self.registers.acc = result as u8;
         self.update_flags(
             &[
@@ -188,19 +190,6 @@ impl CPU {
                 FlagUpdate::Overflow(overflow),
             ]
         );
-        
-        // Adicionar ciclos com base no modo de endereçamento (mesmo que ADC)
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 4, // +1 se cruzar página
-            AddressingModes::AbsoluteIndexedY => 4, // +1 se cruzar página
-            AddressingModes::IndexedIndirect => 6,
-            AddressingModes::IndirectIndexed => 5, // +1 se cruzar página
-            _ => 2,
-        };
     }
 
     fn inc(&mut self, mode: AddressingModes) {
@@ -209,15 +198,6 @@ impl CPU {
         let result = value.wrapping_add(1);
         self.write(address, result);
         self.update_flags(&[FlagUpdate::Zero(result), FlagUpdate::Negative(result)]);
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::ZeroPage => 5,
-            AddressingModes::ZeroPageIndexedX => 6,
-            AddressingModes::Absolute => 6,
-            AddressingModes::AbsoluteIndexedX => 7,
-            _ => 2,
-        };
     }
 
     fn dec(&mut self, mode: AddressingModes) {
@@ -226,15 +206,6 @@ impl CPU {
         let result = value.wrapping_sub(1);
         self.write(address, result);
         self.update_flags(&[FlagUpdate::Zero(result), FlagUpdate::Negative(result)]);
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::ZeroPage => 5,
-            AddressingModes::ZeroPageIndexedX => 6,
-            AddressingModes::Absolute => 6,
-            AddressingModes::AbsoluteIndexedX => 7,
-            _ => 2,
-        };
     }
 
     fn inx(&mut self, _mode: AddressingModes) {
@@ -245,9 +216,6 @@ impl CPU {
                 FlagUpdate::Negative(self.registers.index_x),
             ]
         );
-        
-        // Adicionar ciclos (constante para INX)
-        self.remaining_cycles += 2;
     }
 
     fn dex(&mut self, _mode: AddressingModes) {
@@ -258,9 +226,6 @@ impl CPU {
                 FlagUpdate::Negative(self.registers.index_x),
             ]
         );
-        
-        // Adicionar ciclos (constante para DEX)
-        self.remaining_cycles += 2;
     }
 
     fn iny(&mut self, _mode: AddressingModes) {
@@ -271,9 +236,6 @@ impl CPU {
                 FlagUpdate::Negative(self.registers.index_y),
             ]
         );
-        
-        // Adicionar ciclos (constante para INY)
-        self.remaining_cycles += 2;
     }
 
     fn dey(&mut self, _mode: AddressingModes) {
@@ -284,9 +246,6 @@ impl CPU {
                 FlagUpdate::Negative(self.registers.index_y),
             ]
         );
-        
-        // Adicionar ciclos (constante para DEY)
-        self.remaining_cycles += 2;
     }
 
     // Shift
@@ -301,9 +260,6 @@ impl CPU {
                     FlagUpdate::Carry(carry),
                 ]
             );
-            
-            // Acumulador: 2 ciclos
-            self.remaining_cycles += 2;
         } else {
             let address = self.get_operand_address(mode);
             let value = self.read(address);
@@ -313,15 +269,6 @@ impl CPU {
             self.update_flags(
                 &[FlagUpdate::Zero(result), FlagUpdate::Negative(result), FlagUpdate::Carry(carry)]
             );
-            
-            // Outros modos: varia com o endereçamento
-            self.remaining_cycles += match mode {
-                AddressingModes::ZeroPage => 5,
-                AddressingModes::ZeroPageIndexedX => 6,
-                AddressingModes::Absolute => 6,
-                AddressingModes::AbsoluteIndexedX => 7,
-                _ => 2,
-            };
         }
     }
 
@@ -336,9 +283,6 @@ impl CPU {
                     FlagUpdate::Carry(carry),
                 ]
             );
-            
-            // Acumulador: 2 ciclos
-            self.remaining_cycles += 2;
         } else {
             let address = self.get_operand_address(mode);
             let value = self.read(address);
@@ -348,15 +292,6 @@ impl CPU {
             self.update_flags(
                 &[FlagUpdate::Zero(result), FlagUpdate::Negative(result), FlagUpdate::Carry(carry)]
             );
-            
-            // Outros modos: varia com o endereçamento
-            self.remaining_cycles += match mode {
-                AddressingModes::ZeroPage => 5,
-                AddressingModes::ZeroPageIndexedX => 6,
-                AddressingModes::Absolute => 6,
-                AddressingModes::AbsoluteIndexedX => 7,
-                _ => 2,
-            };
         }
     }
 
@@ -372,9 +307,6 @@ impl CPU {
                     FlagUpdate::Carry(new_carry),
                 ]
             );
-            
-            // Acumulador: 2 ciclos
-            self.remaining_cycles += 2;
         } else {
             let address = self.get_operand_address(mode);
             let value = self.read(address);
@@ -388,15 +320,6 @@ impl CPU {
                     FlagUpdate::Carry(new_carry),
                 ]
             );
-            
-            // Outros modos: varia com o endereçamento
-            self.remaining_cycles += match mode {
-                AddressingModes::ZeroPage => 5,
-                AddressingModes::ZeroPageIndexedX => 6,
-                AddressingModes::Absolute => 6,
-                AddressingModes::AbsoluteIndexedX => 7,
-                _ => 2,
-            };
         }
     }
 
@@ -412,9 +335,6 @@ impl CPU {
                     FlagUpdate::Carry(new_carry),
                 ]
             );
-            
-            // Acumulador: 2 ciclos
-            self.remaining_cycles += 2;
         } else {
             let address = self.get_operand_address(mode);
             let value = self.read(address);
@@ -428,15 +348,6 @@ impl CPU {
                     FlagUpdate::Carry(new_carry),
                 ]
             );
-            
-            // Outros modos: varia com o endereçamento
-            self.remaining_cycles += match mode {
-                AddressingModes::ZeroPage => 5,
-                AddressingModes::ZeroPageIndexedX => 6,
-                AddressingModes::Absolute => 6,
-                AddressingModes::AbsoluteIndexedX => 7,
-                _ => 2,
-            };
         }
     }
 
@@ -444,55 +355,25 @@ impl CPU {
     fn and(&mut self, mode: AddressingModes) {
         let value_for_acc = self.execute_mode(mode);
         self.registers.acc &= value_for_acc;
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 4, // +1 se cruzar página
-            AddressingModes::AbsoluteIndexedY => 4, // +1 se cruzar página
-            AddressingModes::IndexedIndirect => 6,
-            AddressingModes::IndirectIndexed => 5, // +1 se cruzar página
-            _ => 2,
-        };
+        self.update_flags(
+            &[FlagUpdate::Zero(self.registers.acc), FlagUpdate::Negative(self.registers.acc)]
+        );
     }
 
     fn ora(&mut self, mode: AddressingModes) {
         let value_for_acc = self.execute_mode(mode);
         self.registers.acc |= value_for_acc;
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 4, // +1 se cruzar página
-            AddressingModes::AbsoluteIndexedY => 4, // +1 se cruzar página
-            AddressingModes::IndexedIndirect => 6,
-            AddressingModes::IndirectIndexed => 5, // +1 se cruzar página
-            _ => 2,
-        };
+        self.update_flags(
+            &[FlagUpdate::Zero(self.registers.acc), FlagUpdate::Negative(self.registers.acc)]
+        );
     }
 
     fn eor(&mut self, mode: AddressingModes) {
         let value_for_acc = self.execute_mode(mode);
         self.registers.acc ^= value_for_acc;
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 4, // +1 se cruzar página
-            AddressingModes::AbsoluteIndexedY => 4, // +1 se cruzar página
-            AddressingModes::IndexedIndirect => 6,
-            AddressingModes::IndirectIndexed => 5, // +1 se cruzar página
-            _ => 2,
-        };
+        self.update_flags(
+            &[FlagUpdate::Zero(self.registers.acc), FlagUpdate::Negative(self.registers.acc)]
+        );
     }
 
     fn bit(&mut self, mode: AddressingModes) {
@@ -503,16 +384,9 @@ impl CPU {
             &[
                 FlagUpdate::Zero(result),
                 FlagUpdate::Overflow((value & 0x40) != 0),
-                FlagUpdate::Negative(if (value & 0x80) != 0 { 1 } else { 0 }),
+                FlagUpdate::Negative(value),
             ]
         );
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::Absolute => 4,
-            _ => 2,
-        };
     }
 
     // Compare
@@ -527,19 +401,6 @@ impl CPU {
                 FlagUpdate::Carry(self.registers.acc >= value),
             ]
         );
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::ZeroPageIndexedX => 4,
-            AddressingModes::Absolute => 4,
-            AddressingModes::AbsoluteIndexedX => 4, // +1 se cruzar página
-            AddressingModes::AbsoluteIndexedY => 4, // +1 se cruzar página
-            AddressingModes::IndexedIndirect => 6,
-            AddressingModes::IndirectIndexed => 5, // +1 se cruzar página
-            _ => 2,
-        };
     }
 
     fn cpx(&mut self, mode: AddressingModes) {
@@ -553,14 +414,6 @@ impl CPU {
                 FlagUpdate::Carry(self.registers.index_x >= value),
             ]
         );
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::Absolute => 4,
-            _ => 2,
-        };
     }
 
     fn cpy(&mut self, mode: AddressingModes) {
@@ -574,92 +427,53 @@ impl CPU {
                 FlagUpdate::Carry(self.registers.index_y >= value),
             ]
         );
-        
-        // Adicionar ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Immediate => 2,
-            AddressingModes::ZeroPage => 3,
-            AddressingModes::Absolute => 4,
-            _ => 2,
-        };
     }
 
     // Branch
     fn bcc(&mut self, _mode: AddressingModes) {
         let carry_clear = (self.registers.status_register & CARRY) == 0;
         self.branch_if(carry_clear);
-        
-        // Ciclos: 2 (+ 1 se ramificação for tomada, + 1 se cruzar página)
-        self.remaining_cycles += 2;
     }
 
     fn bcs(&mut self, _mode: AddressingModes) {
         let carry_set = (self.registers.status_register & CARRY) != 0;
         self.branch_if(carry_set);
-        
-        // Ciclos: 2 (+ 1 se ramificação for tomada, + 1 se cruzar página)
-        self.remaining_cycles += 2;
     }
 
     fn beq(&mut self, _mode: AddressingModes) {
         let zero_set = (self.registers.status_register & ZERO) != 0;
         self.branch_if(zero_set);
-        
-        // Ciclos: 2 (+ 1 se ramificação for tomada, + 1 se cruzar página)
-        self.remaining_cycles += 2;
     }
 
     fn bne(&mut self, _mode: AddressingModes) {
         let zero_clear = (self.registers.status_register & ZERO) == 0;
         self.branch_if(zero_clear);
-        
-        // Ciclos: 2 (+ 1 se ramificação for tomada, + 1 se cruzar página)
-        self.remaining_cycles += 2;
     }
 
     fn bpl(&mut self, _mode: AddressingModes) {
         let negative_clear = (self.registers.status_register & NEGATIVE) == 0;
         self.branch_if(negative_clear);
-        
-        // Ciclos: 2 (+ 1 se ramificação for tomada, + 1 se cruzar página)
-        self.remaining_cycles += 2;
     }
 
     fn bmi(&mut self, _mode: AddressingModes) {
         let negative_set = (self.registers.status_register & NEGATIVE) != 0;
         self.branch_if(negative_set);
-        
-        // Ciclos: 2 (+ 1 se ramificação for tomada, + 1 se cruzar página)
-        self.remaining_cycles += 2;
     }
 
     fn bvc(&mut self, _mode: AddressingModes) {
         let overflow_clear = (self.registers.status_register & OVERFLOW) == 0;
         self.branch_if(overflow_clear);
-        
-        // Ciclos: 2 (+ 1 se ramificação for tomada, + 1 se cruzar página)
-        self.remaining_cycles += 2;
     }
 
     fn bvs(&mut self, _mode: AddressingModes) {
         let overflow_set = (self.registers.status_register & OVERFLOW) != 0;
         self.branch_if(overflow_set);
-        
-        // Ciclos: 2 (+ 1 se ramificação for tomada, + 1 se cruzar página)
-        self.remaining_cycles += 2;
     }
 
     // Jump
     fn jmp(&mut self, mode: AddressingModes) {
         let address = self.get_operand_address(mode);
         self.registers.program_counter = address;
-        
-        // Ciclos com base no modo de endereçamento
-        self.remaining_cycles += match mode {
-            AddressingModes::Absolute => 3,
-            AddressingModes::Indirect => 5,
-            _ => 3,
-        };
     }
 
     fn jsr(&mut self, _mode: AddressingModes) {
@@ -681,9 +495,6 @@ impl CPU {
 
         // Pula para o endereço
         self.registers.program_counter = jump_addr;
-        
-        // Ciclos para JSR
-        self.remaining_cycles += 6;
     }
 
     fn rts(&mut self, _mode: AddressingModes) {
@@ -697,106 +508,329 @@ impl CPU {
 
         // Retorna para o endereço + 1
         self.registers.program_counter = return_addr.wrapping_add(1);
-
-        // Ciclos para RTS
-        self.remaining_cycles += 6;
     }
 
     fn brk(&mut self, _mode: AddressingModes) {
-        // ...existing code...
-
-        // Ciclos para BRK
-        self.remaining_cycles += 7;
+        // BRK pula um byte de padding e empilha o status com a flag B setada
+        self.push_u16(self.registers.program_counter.wrapping_add(1));
+        self.push(self.registers.status_register | BREAK | UNUSED);
+        self.registers.status_register |= INTERRUPT_DISABLE;
+        self.clear_decimal_on_interrupt();
+        self.registers.program_counter = self.read_u16(0xfffe);
     }
 
     fn rti(&mut self, _mode: AddressingModes) {
-        // ...existing code...
-
-        // Ciclos para RTI
-        self.remaining_cycles += 6;
+        // B não existe no registrador; o bit 5 sempre lê como 1
+        self.registers.status_register = (self.pull() & !BREAK) | UNUSED;
+        self.registers.program_counter = self.pull_u16();
     }
 
     fn pha(&mut self, _mode: AddressingModes) {
         self.write(0x100 + (self.registers.stack_pointer as usize as u16), self.registers.acc);
         self.registers.stack_pointer = self.registers.stack_pointer.wrapping_sub(1);
-        
-        // Ciclos para PHA
-        self.remaining_cycles += 3;
     }
 
     fn pla(&mut self, _mode: AddressingModes) {
-        // ...existing code...
-
-        // Ciclos para PLA
-        self.remaining_cycles += 4;
+        self.registers.acc = self.pull();
+        self.update_flags(
+            &[FlagUpdate::Zero(self.registers.acc), FlagUpdate::Negative(self.registers.acc)]
+        );
     }
 
     fn php(&mut self, _mode: AddressingModes) {
-        // ...existing code...
-
-        // Ciclos para PHP
-        self.remaining_cycles += 3;
+        self.push(self.registers.status_register | BREAK | UNUSED);
     }
 
     fn plp(&mut self, _mode: AddressingModes) {
-        // ...existing code...
-
-        // Ciclos para PLP
-        self.remaining_cycles += 4;
+        self.registers.status_register = (self.pull() & !BREAK) | UNUSED;
     }
 
     fn txs(&mut self, _mode: AddressingModes) {
         self.registers.stack_pointer = self.registers.index_x;
-        
-        // Ciclos para TXS
-        self.remaining_cycles += 2;
     }
 
     fn tsx(&mut self, _mode: AddressingModes) {
-        // ...existing code...
-
-        // Ciclos para TSX
-        self.remaining_cycles += 2;
+        self.registers.index_x = self.registers.stack_pointer;
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(self.registers.index_x),
+                FlagUpdate::Negative(self.registers.index_x),
+            ]
+        );
     }
 
     // Flags - todas as operações de flag levam 2 ciclos
     fn clc(&mut self, _mode: AddressingModes) {
         self.registers.status_register &= !CARRY;
-        self.remaining_cycles += 2;
     }
 
     fn sec(&mut self, _mode: AddressingModes) {
         self.registers.status_register |= CARRY;
-        self.remaining_cycles += 2;
     }
 
     fn cli(&mut self, _mode: AddressingModes) {
         self.registers.status_register &= !INTERRUPT_DISABLE;
-        self.remaining_cycles += 2;
     }
 
     fn sei(&mut self, _mode: AddressingModes) {
         self.registers.status_register |= INTERRUPT_DISABLE;
-        self.remaining_cycles += 2;
     }
 
     fn cld(&mut self, _mode: AddressingModes) {
         self.registers.status_register &= !DECIMAL;
-        self.remaining_cycles += 2;
     }
 
     fn sed(&mut self, _mode: AddressingModes) {
         self.registers.status_register |= DECIMAL;
-        self.remaining_cycles += 2;
     }
 
     fn clv(&mut self, _mode: AddressingModes) {
         self.registers.status_register &= !OVERFLOW;
-        self.remaining_cycles += 2;
     }
 
-    fn nop(&mut self, _mode: AddressingModes) {
-        // NOP leva 2 ciclos
-        self.remaining_cycles += 2;
+    fn nop(&mut self, mode: AddressingModes) {
+        // Só consome os ciclos da tabela OPCODES; os NOPs não oficiais ainda leem o operando
+        if mode != AddressingModes::Implicit {
+            self.execute_mode(mode);
+        }
+    }
+
+    // Não oficiais (estáveis)
+    fn slo(&mut self, mode: AddressingModes) {
+        // ASL na memória + ORA
+        let address = self.get_operand_address(mode);
+        let value = self.read(address);
+        let result = value << 1;
+        self.write(address, result);
+        self.registers.acc |= result;
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(self.registers.acc),
+                FlagUpdate::Negative(self.registers.acc),
+                FlagUpdate::Carry((value & 0x80) != 0),
+            ]
+        );
+    }
+
+    fn rla(&mut self, mode: AddressingModes) {
+        // ROL na memória + AND
+        let address = self.get_operand_address(mode);
+        let value = self.read(address);
+        let old_carry = (self.registers.status_register & CARRY) != 0;
+        let result = (value << 1) | (if old_carry { 1 } else { 0 });
+        self.write(address, result);
+        self.registers.acc &= result;
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(self.registers.acc),
+                FlagUpdate::Negative(self.registers.acc),
+                FlagUpdate::Carry((value & 0x80) != 0),
+            ]
+        );
+    }
+
+    fn sre(&mut self, mode: AddressingModes) {
+        // LSR na memória + EOR
+        let address = self.get_operand_address(mode);
+        let value = self.read(address);
+        let result = value >> 1;
+        self.write(address, result);
+        self.registers.acc ^= result;
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(self.registers.acc),
+                FlagUpdate::Negative(self.registers.acc),
+                FlagUpdate::Carry((value & 0x01) != 0),
+            ]
+        );
+    }
+
+    fn rra(&mut self, mode: AddressingModes) {
+        // ROR na memória + ADC (usando o carry que saiu do ROR)
+        let address = self.get_operand_address(mode);
+        let value = self.read(address);
+        let old_carry = (self.registers.status_register & CARRY) != 0;
+        let result = (value >> 1) | (if old_carry { 0x80 } else { 0 });
+        self.write(address, result);
+        self.update_flags(&[FlagUpdate::Carry((value & 0x01) != 0)]);
+        self.adc_value(result);
+    }
+
+    fn sax(&mut self, mode: AddressingModes) {
+        let address = self.get_operand_address(mode);
+        self.write(address, self.registers.acc & self.registers.index_x);
+    }
+
+    fn lax(&mut self, mode: AddressingModes) {
+        let value = self.execute_mode(mode);
+        self.registers.acc = value;
+        self.registers.index_x = value;
+        self.update_flags(&[FlagUpdate::Zero(value), FlagUpdate::Negative(value)]);
+    }
+
+    fn dcp(&mut self, mode: AddressingModes) {
+        // DEC na memória + CMP
+        let address = self.get_operand_address(mode);
+        let result = self.read(address).wrapping_sub(1);
+        self.write(address, result);
+        let diff = self.registers.acc.wrapping_sub(result);
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(diff),
+                FlagUpdate::Negative(diff),
+                FlagUpdate::Carry(self.registers.acc >= result),
+            ]
+        );
+    }
+
+    fn isc(&mut self, mode: AddressingModes) {
+        // INC na memória + SBC
+        let address = self.get_operand_address(mode);
+        let result = self.read(address).wrapping_add(1);
+        self.write(address, result);
+        self.sbc_value(result);
+    }
+
+    fn anc(&mut self, mode: AddressingModes) {
+        let value = self.execute_mode(mode);
+        self.registers.acc &= value;
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(self.registers.acc),
+                FlagUpdate::Negative(self.registers.acc),
+                FlagUpdate::Carry((self.registers.acc & 0x80) != 0),
+            ]
+        );
+    }
+
+    fn alr(&mut self, mode: AddressingModes) {
+        // AND #imm + LSR A
+        let value = self.execute_mode(mode) & self.registers.acc;
+        self.registers.acc = value >> 1;
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(self.registers.acc),
+                FlagUpdate::Negative(self.registers.acc),
+                FlagUpdate::Carry((value & 0x01) != 0),
+            ]
+        );
+    }
+
+    fn arr(&mut self, mode: AddressingModes) {
+        // AND #imm + ROR A, com C e V vindos dos bits 6 e 5 do resultado
+        let value = self.execute_mode(mode) & self.registers.acc;
+        let old_carry = (self.registers.status_register & CARRY) != 0;
+        self.registers.acc = (value >> 1) | (if old_carry { 0x80 } else { 0 });
+        let bit6 = (self.registers.acc & 0x40) != 0;
+        let bit5 = (self.registers.acc & 0x20) != 0;
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(self.registers.acc),
+                FlagUpdate::Negative(self.registers.acc),
+                FlagUpdate::Carry(bit6),
+                FlagUpdate::Overflow(bit6 ^ bit5),
+            ]
+        );
+    }
+
+    fn axs(&mut self, mode: AddressingModes) {
+        // X = (A & X) - #imm, sem borrow
+        let value = self.execute_mode(mode);
+        let and = self.registers.acc & self.registers.index_x;
+        self.registers.index_x = and.wrapping_sub(value);
+        self.update_flags(
+            &[
+                FlagUpdate::Zero(self.registers.index_x),
+                FlagUpdate::Negative(self.registers.index_x),
+                FlagUpdate::Carry(and >= value),
+            ]
+        );
+    }
+
+    // Não oficiais (instáveis): dependem de IllegalOpcodePolicy
+    fn xaa(&mut self, mode: AddressingModes) {
+        if !self.unstable_opcode("XAA", mode) {
+            return;
+        }
+        // A constante "mágica" varia entre chips; 0xEE é a mais comum
+        let value = self.execute_mode(mode);
+        self.registers.acc = (self.registers.acc | 0xee) & self.registers.index_x & value;
+        self.update_flags(
+            &[FlagUpdate::Zero(self.registers.acc), FlagUpdate::Negative(self.registers.acc)]
+        );
+    }
+
+    fn lxa(&mut self, mode: AddressingModes) {
+        if !self.unstable_opcode("LXA", mode) {
+            return;
+        }
+        let value = (self.registers.acc | 0xee) & self.execute_mode(mode);
+        self.registers.acc = value;
+        self.registers.index_x = value;
+        self.update_flags(&[FlagUpdate::Zero(value), FlagUpdate::Negative(value)]);
+    }
+
+    fn sha(&mut self, mode: AddressingModes) {
+        if !self.unstable_opcode("SHA", mode) {
+            return;
+        }
+        let value = self.registers.acc & self.registers.index_x;
+        self.store_and_high(mode, value);
+    }
+
+    fn shx(&mut self, mode: AddressingModes) {
+        if !self.unstable_opcode("SHX", mode) {
+            return;
+        }
+        self.store_and_high(mode, self.registers.index_x);
+    }
+
+    fn shy(&mut self, mode: AddressingModes) {
+        if !self.unstable_opcode("SHY", mode) {
+            return;
+        }
+        self.store_and_high(mode, self.registers.index_y);
+    }
+
+    fn tas(&mut self, mode: AddressingModes) {
+        if !self.unstable_opcode("TAS", mode) {
+            return;
+        }
+        self.registers.stack_pointer = self.registers.acc & self.registers.index_x;
+        self.store_and_high(mode, self.registers.stack_pointer);
+    }
+
+    fn las(&mut self, mode: AddressingModes) {
+        if !self.unstable_opcode("LAS", mode) {
+            return;
+        }
+        let value = self.execute_mode(mode) & self.registers.stack_pointer;
+        self.registers.acc = value;
+        self.registers.index_x = value;
+        self.registers.stack_pointer = value;
+        self.update_flags(&[FlagUpdate::Zero(value), FlagUpdate::Negative(value)]);
+    }
+
+    fn jam(&mut self, _mode: AddressingModes) {
+        self.jam_opcode();
+    }
+
+    // SHA/SHX/SHY/TAS gravam `value & (H + 1)`, onde H é o byte alto do endereço base
+    fn store_and_high(&mut self, mode: AddressingModes, value: u8) {
+        let address = self.get_operand_address(mode);
+        let index = match mode {
+            AddressingModes::AbsoluteIndexedX => self.registers.index_x,
+            _ => self.registers.index_y,
+        };
+        let base = address.wrapping_sub(index as u16);
+        let high = ((base >> 8) as u8).wrapping_add(1);
+        let result = value & high;
+        // Quando cruza a página o byte alto do endereço também é corrompido
+        let address = if (base & 0xff00) != (address & 0xff00) {
+            ((result as u16) << 8) | (address & 0x00ff)
+        } else {
+            address
+        };
+        self.write(address, result);
     }
-}
\ No newline at end of file
+}
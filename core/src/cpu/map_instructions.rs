@@ -0,0 +1,313 @@
+/// Entrada da tabela de dispatch: tudo o que o executor, o disassembler e o modelo de
+/// ciclos precisam saber sobre um opcode.
+#[derive(Clone, Copy)]
+pub struct OpcodeInfo {
+    pub function: Instruction,
+    pub mode: AddressingModes,
+    pub cycles: u8,   // Ciclos base; página cruzada e branch tomado somam à parte (penalty_cycles)
+    pub bytes: u8,    // Tamanho da instrução, incluindo o opcode
+    pub mnemonic: &'static str,
+    pub official: bool,
+}
+
+const fn op(
+    mnemonic: &'static str,
+    function: Instruction,
+    mode: AddressingModes,
+    cycles: u8
+) -> OpcodeInfo {
+    OpcodeInfo {
+        function,
+        mode,
+        cycles,
+        bytes: instruction_bytes(mode),
+        mnemonic,
+        official: true,
+    }
+}
+
+const fn unofficial(
+    mnemonic: &'static str,
+    function: Instruction,
+    mode: AddressingModes,
+    cycles: u8
+) -> OpcodeInfo {
+    let mut info = op(mnemonic, function, mode, cycles);
+    info.official = false;
+    info
+}
+
+const fn instruction_bytes(mode: AddressingModes) -> u8 {
+    match mode {
+        AddressingModes::Implicit | AddressingModes::Accumulator => 1,
+        AddressingModes::Absolute
+        | AddressingModes::AbsoluteIndexedX
+        | AddressingModes::AbsoluteIndexedY
+        | AddressingModes::Indirect => 3,
+        _ => 2,
+    }
+}
+
+/// Tabela de dispatch indexada pelo opcode (256 entradas, incluindo os não oficiais e JAM).
+/// Os mnemônicos não oficiais seguem a nomenclatura do log do nestest (ISB, DCP, ...).
+pub static OPCODES: [OpcodeInfo; 256] = {
+    use AddressingModes::*;
+    [
+        /* 0x00 */ op("BRK", CPU::brk, Implicit, 7),
+        /* 0x01 */ op("ORA", CPU::ora, IndexedIndirect, 6),
+        /* 0x02 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x03 */ unofficial("SLO", CPU::slo, IndexedIndirect, 8),
+        /* 0x04 */ unofficial("NOP", CPU::nop, ZeroPage, 3),
+        /* 0x05 */ op("ORA", CPU::ora, ZeroPage, 3),
+        /* 0x06 */ op("ASL", CPU::asl, ZeroPage, 5),
+        /* 0x07 */ unofficial("SLO", CPU::slo, ZeroPage, 5),
+        /* 0x08 */ op("PHP", CPU::php, Implicit, 3),
+        /* 0x09 */ op("ORA", CPU::ora, Immediate, 2),
+        /* 0x0A */ op("ASL", CPU::asl, Accumulator, 2),
+        /* 0x0B */ unofficial("ANC", CPU::anc, Immediate, 2),
+        /* 0x0C */ unofficial("NOP", CPU::nop, Absolute, 4),
+        /* 0x0D */ op("ORA", CPU::ora, Absolute, 4),
+        /* 0x0E */ op("ASL", CPU::asl, Absolute, 6),
+        /* 0x0F */ unofficial("SLO", CPU::slo, Absolute, 6),
+        /* 0x10 */ op("BPL", CPU::bpl, Relative, 2),
+        /* 0x11 */ op("ORA", CPU::ora, IndirectIndexed, 5),
+        /* 0x12 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x13 */ unofficial("SLO", CPU::slo, IndirectIndexed, 8),
+        /* 0x14 */ unofficial("NOP", CPU::nop, ZeroPageIndexedX, 4),
+        /* 0x15 */ op("ORA", CPU::ora, ZeroPageIndexedX, 4),
+        /* 0x16 */ op("ASL", CPU::asl, ZeroPageIndexedX, 6),
+        /* 0x17 */ unofficial("SLO", CPU::slo, ZeroPageIndexedX, 6),
+        /* 0x18 */ op("CLC", CPU::clc, Implicit, 2),
+        /* 0x19 */ op("ORA", CPU::ora, AbsoluteIndexedY, 4),
+        /* 0x1A */ unofficial("NOP", CPU::nop, Implicit, 2),
+        /* 0x1B */ unofficial("SLO", CPU::slo, AbsoluteIndexedY, 7),
+        /* 0x1C */ unofficial("NOP", CPU::nop, AbsoluteIndexedX, 4),
+        /* 0x1D */ op("ORA", CPU::ora, AbsoluteIndexedX, 4),
+        /* 0x1E */ op("ASL", CPU::asl, AbsoluteIndexedX, 7),
+        /* 0x1F */ unofficial("SLO", CPU::slo, AbsoluteIndexedX, 7),
+        /* 0x20 */ op("JSR", CPU::jsr, Absolute, 6),
+        /* 0x21 */ op("AND", CPU::and, IndexedIndirect, 6),
+        /* 0x22 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x23 */ unofficial("RLA", CPU::rla, IndexedIndirect, 8),
+        /* 0x24 */ op("BIT", CPU::bit, ZeroPage, 3),
+        /* 0x25 */ op("AND", CPU::and, ZeroPage, 3),
+        /* 0x26 */ op("ROL", CPU::rol, ZeroPage, 5),
+        /* 0x27 */ unofficial("RLA", CPU::rla, ZeroPage, 5),
+        /* 0x28 */ op("PLP", CPU::plp, Implicit, 4),
+        /* 0x29 */ op("AND", CPU::and, Immediate, 2),
+        /* 0x2A */ op("ROL", CPU::rol, Accumulator, 2),
+        /* 0x2B */ unofficial("ANC", CPU::anc, Immediate, 2),
+        /* 0x2C */ op("BIT", CPU::bit, Absolute, 4),
+        /* 0x2D */ op("AND", CPU::and, Absolute, 4),
+        /* 0x2E */ op("ROL", CPU::rol, Absolute, 6),
+        /* 0x2F */ unofficial("RLA", CPU::rla, Absolute, 6),
+        /* 0x30 */ op("BMI", CPU::bmi, Relative, 2),
+        /* 0x31 */ op("AND", CPU::and, IndirectIndexed, 5),
+        /* 0x32 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x33 */ unofficial("RLA", CPU::rla, IndirectIndexed, 8),
+        /* 0x34 */ unofficial("NOP", CPU::nop, ZeroPageIndexedX, 4),
+        /* 0x35 */ op("AND", CPU::and, ZeroPageIndexedX, 4),
+        /* 0x36 */ op("ROL", CPU::rol, ZeroPageIndexedX, 6),
+        /* 0x37 */ unofficial("RLA", CPU::rla, ZeroPageIndexedX, 6),
+        /* 0x38 */ op("SEC", CPU::sec, Implicit, 2),
+        /* 0x39 */ op("AND", CPU::and, AbsoluteIndexedY, 4),
+        /* 0x3A */ unofficial("NOP", CPU::nop, Implicit, 2),
+        /* 0x3B */ unofficial("RLA", CPU::rla, AbsoluteIndexedY, 7),
+        /* 0x3C */ unofficial("NOP", CPU::nop, AbsoluteIndexedX, 4),
+        /* 0x3D */ op("AND", CPU::and, AbsoluteIndexedX, 4),
+        /* 0x3E */ op("ROL", CPU::rol, AbsoluteIndexedX, 7),
+        /* 0x3F */ unofficial("RLA", CPU::rla, AbsoluteIndexedX, 7),
+        /* 0x40 */ op("RTI", CPU::rti, Implicit, 6),
+        /* 0x41 */ op("EOR", CPU::eor, IndexedIndirect, 6),
+        /* 0x42 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x43 */ unofficial("SRE", CPU::sre, IndexedIndirect, 8),
+        /* 0x44 */ unofficial("NOP", CPU::nop, ZeroPage, 3),
+        /* 0x45 */ op("EOR", CPU::eor, ZeroPage, 3),
+        /* 0x46 */ op("LSR", CPU::lsr, ZeroPage, 5),
+        /* 0x47 */ unofficial("SRE", CPU::sre, ZeroPage, 5),
+        /* 0x48 */ op("PHA", CPU::pha, Implicit, 3),
+        /* 0x49 */ op("EOR", CPU::eor, Immediate, 2),
+        /* 0x4A */ op("LSR", CPU::lsr, Accumulator, 2),
+        /* 0x4B */ unofficial("ALR", CPU::alr, Immediate, 2),
+        /* 0x4C */ op("JMP", CPU::jmp, Absolute, 3),
+        /* 0x4D */ op("EOR", CPU::eor, Absolute, 4),
+        /* 0x4E */ op("LSR", CPU::lsr, Absolute, 6),
+        /* 0x4F */ unofficial("SRE", CPU::sre, Absolute, 6),
+        /* 0x50 */ op("BVC", CPU::bvc, Relative, 2),
+        /* 0x51 */ op("EOR", CPU::eor, IndirectIndexed, 5),
+        /* 0x52 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x53 */ unofficial("SRE", CPU::sre, IndirectIndexed, 8),
+        /* 0x54 */ unofficial("NOP", CPU::nop, ZeroPageIndexedX, 4),
+        /* 0x55 */ op("EOR", CPU::eor, ZeroPageIndexedX, 4),
+        /* 0x56 */ op("LSR", CPU::lsr, ZeroPageIndexedX, 6),
+        /* 0x57 */ unofficial("SRE", CPU::sre, ZeroPageIndexedX, 6),
+        /* 0x58 */ op("CLI", CPU::cli, Implicit, 2),
+        /* 0x59 */ op("EOR", CPU::eor, AbsoluteIndexedY, 4),
+        /* 0x5A */ unofficial("NOP", CPU::nop, Implicit, 2),
+        /* 0x5B */ unofficial("SRE", CPU::sre, AbsoluteIndexedY, 7),
+        /* 0x5C */ unofficial("NOP", CPU::nop, AbsoluteIndexedX, 4),
+        /* 0x5D */ op("EOR", CPU::eor, AbsoluteIndexedX, 4),
+        /* 0x5E */ op("LSR", CPU::lsr, AbsoluteIndexedX, 7),
+        /* 0x5F */ unofficial("SRE", CPU::sre, AbsoluteIndexedX, 7),
+        /* 0x60 */ op("RTS", CPU::rts, Implicit, 6),
+        /* 0x61 */ op("ADC", CPU::adc, IndexedIndirect, 6),
+        /* 0x62 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x63 */ unofficial("RRA", CPU::rra, IndexedIndirect, 8),
+        /* 0x64 */ unofficial("NOP", CPU::nop, ZeroPage, 3),
+        /* 0x65 */ op("ADC", CPU::adc, ZeroPage, 3),
+        /* 0x66 */ op("ROR", CPU::ror, ZeroPage, 5),
+        /* 0x67 */ unofficial("RRA", CPU::rra, ZeroPage, 5),
+        /* 0x68 */ op("PLA", CPU::pla, Implicit, 4),
+        /* 0x69 */ op("ADC", CPU::adc, Immediate, 2),
+        /* 0x6A */ op("ROR", CPU::ror, Accumulator, 2),
+        /* 0x6B */ unofficial("ARR", CPU::arr, Immediate, 2),
+        /* 0x6C */ op("JMP", CPU::jmp, Indirect, 5),
+        /* 0x6D */ op("ADC", CPU::adc, Absolute, 4),
+        /* 0x6E */ op("ROR", CPU::ror, Absolute, 6),
+        /* 0x6F */ unofficial("RRA", CPU::rra, Absolute, 6),
+        /* 0x70 */ op("BVS", CPU::bvs, Relative, 2),
+        /* 0x71 */ op("ADC", CPU::adc, IndirectIndexed, 5),
+        /* 0x72 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x73 */ unofficial("RRA", CPU::rra, IndirectIndexed, 8),
+        /* 0x74 */ unofficial("NOP", CPU::nop, ZeroPageIndexedX, 4),
+        /* 0x75 */ op("ADC", CPU::adc, ZeroPageIndexedX, 4),
+        /* 0x76 */ op("ROR", CPU::ror, ZeroPageIndexedX, 6),
+        /* 0x77 */ unofficial("RRA", CPU::rra, ZeroPageIndexedX, 6),
+        /* 0x78 */ op("SEI", CPU::sei, Implicit, 2),
+        /* 0x79 */ op("ADC", CPU::adc, AbsoluteIndexedY, 4),
+        /* 0x7A */ unofficial("NOP", CPU::nop, Implicit, 2),
+        /* 0x7B */ unofficial("RRA", CPU::rra, AbsoluteIndexedY, 7),
+        /* 0x7C */ unofficial("NOP", CPU::nop, AbsoluteIndexedX, 4),
+        /* 0x7D */ op("ADC", CPU::adc, AbsoluteIndexedX, 4),
+        /* 0x7E */ op("ROR", CPU::ror, AbsoluteIndexedX, 7),
+        /* 0x7F */ unofficial("RRA", CPU::rra, AbsoluteIndexedX, 7),
+        /* 0x80 */ unofficial("NOP", CPU::nop, Immediate, 2),
+        /* 0x81 */ op("STA", CPU::sta, IndexedIndirect, 6),
+        /* 0x82 */ unofficial("NOP", CPU::nop, Immediate, 2),
+        /* 0x83 */ unofficial("SAX", CPU::sax, IndexedIndirect, 6),
+        /* 0x84 */ op("STY", CPU::sty, ZeroPage, 3),
+        /* 0x85 */ op("STA", CPU::sta, ZeroPage, 3),
+        /* 0x86 */ op("STX", CPU::stx, ZeroPage, 3),
+        /* 0x87 */ unofficial("SAX", CPU::sax, ZeroPage, 3),
+        /* 0x88 */ op("DEY", CPU::dey, Implicit, 2),
+        /* 0x89 */ unofficial("NOP", CPU::nop, Immediate, 2),
+        /* 0x8A */ op("TXA", CPU::txa, Implicit, 2),
+        /* 0x8B */ unofficial("XAA", CPU::xaa, Immediate, 2),
+        /* 0x8C */ op("STY", CPU::sty, Absolute, 4),
+        /* 0x8D */ op("STA", CPU::sta, Absolute, 4),
+        /* 0x8E */ op("STX", CPU::stx, Absolute, 4),
+        /* 0x8F */ unofficial("SAX", CPU::sax, Absolute, 4),
+        /* 0x90 */ op("BCC", CPU::bcc, Relative, 2),
+        /* 0x91 */ op("STA", CPU::sta, IndirectIndexed, 6),
+        /* 0x92 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0x93 */ unofficial("SHA", CPU::sha, IndirectIndexed, 6),
+        /* 0x94 */ op("STY", CPU::sty, ZeroPageIndexedX, 4),
+        /* 0x95 */ op("STA", CPU::sta, ZeroPageIndexedX, 4),
+        /* 0x96 */ op("STX", CPU::stx, ZeroPageIndexedY, 4),
+        /* 0x97 */ unofficial("SAX", CPU::sax, ZeroPageIndexedY, 4),
+        /* 0x98 */ op("TYA", CPU::tya, Implicit, 2),
+        /* 0x99 */ op("STA", CPU::sta, AbsoluteIndexedY, 5),
+        /* 0x9A */ op("TXS", CPU::txs, Implicit, 2),
+        /* 0x9B */ unofficial("TAS", CPU::tas, AbsoluteIndexedY, 5),
+        /* 0x9C */ unofficial("SHY", CPU::shy, AbsoluteIndexedX, 5),
+        /* 0x9D */ op("STA", CPU::sta, AbsoluteIndexedX, 5),
+        /* 0x9E */ unofficial("SHX", CPU::shx, AbsoluteIndexedY, 5),
+        /* 0x9F */ unofficial("SHA", CPU::sha, AbsoluteIndexedY, 5),
+        /* 0xA0 */ op("LDY", CPU::ldy, Immediate, 2),
+        /* 0xA1 */ op("LDA", CPU::lda, IndexedIndirect, 6),
+        /* 0xA2 */ op("LDX", CPU::ldx, Immediate, 2),
+        /* 0xA3 */ unofficial("LAX", CPU::lax, IndexedIndirect, 6),
+        /* 0xA4 */ op("LDY", CPU::ldy, ZeroPage, 3),
+        /* 0xA5 */ op("LDA", CPU::lda, ZeroPage, 3),
+        /* 0xA6 */ op("LDX", CPU::ldx, ZeroPage, 3),
+        /* 0xA7 */ unofficial("LAX", CPU::lax, ZeroPage, 3),
+        /* 0xA8 */ op("TAY", CPU::tay, Implicit, 2),
+        /* 0xA9 */ op("LDA", CPU::lda, Immediate, 2),
+        /* 0xAA */ op("TAX", CPU::tax, Implicit, 2),
+        /* 0xAB */ unofficial("LXA", CPU::lxa, Immediate, 2),
+        /* 0xAC */ op("LDY", CPU::ldy, Absolute, 4),
+        /* 0xAD */ op("LDA", CPU::lda, Absolute, 4),
+        /* 0xAE */ op("LDX", CPU::ldx, Absolute, 4),
+        /* 0xAF */ unofficial("LAX", CPU::lax, Absolute, 4),
+        /* 0xB0 */ op("BCS", CPU::bcs, Relative, 2),
+        /* 0xB1 */ op("LDA", CPU::lda, IndirectIndexed, 5),
+        /* 0xB2 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0xB3 */ unofficial("LAX", CPU::lax, IndirectIndexed, 5),
+        /* 0xB4 */ op("LDY", CPU::ldy, ZeroPageIndexedX, 4),
+        /* 0xB5 */ op("LDA", CPU::lda, ZeroPageIndexedX, 4),
+        /* 0xB6 */ op("LDX", CPU::ldx, ZeroPageIndexedY, 4),
+        /* 0xB7 */ unofficial("LAX", CPU::lax, ZeroPageIndexedY, 4),
+        /* 0xB8 */ op("CLV", CPU::clv, Implicit, 2),
+        /* 0xB9 */ op("LDA", CPU::lda, AbsoluteIndexedY, 4),
+        /* 0xBA */ op("TSX", CPU::tsx, Implicit, 2),
+        /* 0xBB */ unofficial("LAS", CPU::las, AbsoluteIndexedY, 4),
+        /* 0xBC */ op("LDY", CPU::ldy, AbsoluteIndexedX, 4),
+        /* 0xBD */ op("LDA", CPU::lda, AbsoluteIndexedX, 4),
+        /* 0xBE */ op("LDX", CPU::ldx, AbsoluteIndexedY, 4),
+        /* 0xBF */ unofficial("LAX", CPU::lax, AbsoluteIndexedY, 4),
+        /* 0xC0 */ op("CPY", CPU::cpy, Immediate, 2),
+        /* 0xC1 */ op("CMP", CPU::cmp, IndexedIndirect, 6),
+        /* 0xC2 */ unofficial("NOP", CPU::nop, Immediate, 2),
+        /* 0xC3 */ unofficial("DCP", CPU::dcp, IndexedIndirect, 8),
+        /* 0xC4 */ op("CPY", CPU::cpy, ZeroPage, 3),
+        /* 0xC5 */ op("CMP", CPU::cmp, ZeroPage, 3),
+        /* 0xC6 */ op("DEC", CPU::dec, ZeroPage, 5),
+        /* 0xC7 */ unofficial("DCP", CPU::dcp, ZeroPage, 5),
+        /* 0xC8 */ op("INY", CPU::iny, Implicit, 2),
+        /* 0xC9 */ op("CMP", CPU::cmp, Immediate, 2),
+        /* 0xCA */ op("DEX", CPU::dex, Implicit, 2),
+        /* 0xCB */ unofficial("AXS", CPU::axs, Immediate, 2),
+        /* 0xCC */ op("CPY", CPU::cpy, Absolute, 4),
+        /* 0xCD */ op("CMP", CPU::cmp, Absolute, 4),
+        /* 0xCE */ op("DEC", CPU::dec, Absolute, 6),
+        /* 0xCF */ unofficial("DCP", CPU::dcp, Absolute, 6),
+        /* 0xD0 */ op("BNE", CPU::bne, Relative, 2),
+        /* 0xD1 */ op("CMP", CPU::cmp, IndirectIndexed, 5),
+        /* 0xD2 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0xD3 */ unofficial("DCP", CPU::dcp, IndirectIndexed, 8),
+        /* 0xD4 */ unofficial("NOP", CPU::nop, ZeroPageIndexedX, 4),
+        /* 0xD5 */ op("CMP", CPU::cmp, ZeroPageIndexedX, 4),
+        /* 0xD6 */ op("DEC", CPU::dec, ZeroPageIndexedX, 6),
+        /* 0xD7 */ unofficial("DCP", CPU::dcp, ZeroPageIndexedX, 6),
+        /* 0xD8 */ op("CLD", CPU::cld, Implicit, 2),
+        /* 0xD9 */ op("CMP", CPU::cmp, AbsoluteIndexedY, 4),
+        /* 0xDA */ unofficial("NOP", CPU::nop, Implicit, 2),
+        /* 0xDB */ unofficial("DCP", CPU::dcp, AbsoluteIndexedY, 7),
+        /* 0xDC */ unofficial("NOP", CPU::nop, AbsoluteIndexedX, 4),
+        /* 0xDD */ op("CMP", CPU::cmp, AbsoluteIndexedX, 4),
+        /* 0xDE */ op("DEC", CPU::dec, AbsoluteIndexedX, 7),
+        /* 0xDF */ unofficial("DCP", CPU::dcp, AbsoluteIndexedX, 7),
+        /* 0xE0 */ op("CPX", CPU::cpx, Immediate, 2),
+        /* 0xE1 */ op("SBC", CPU::sbc, IndexedIndirect, 6),
+        /* 0xE2 */ unofficial("NOP", CPU::nop, Immediate, 2),
+        /* 0xE3 */ unofficial("ISB", CPU::isc, IndexedIndirect, 8),
+        /* 0xE4 */ op("CPX", CPU::cpx, ZeroPage, 3),
+        /* 0xE5 */ op("SBC", CPU::sbc, ZeroPage, 3),
+        /* 0xE6 */ op("INC", CPU::inc, ZeroPage, 5),
+        /* 0xE7 */ unofficial("ISB", CPU::isc, ZeroPage, 5),
+        /* 0xE8 */ op("INX", CPU::inx, Implicit, 2),
+        /* 0xE9 */ op("SBC", CPU::sbc, Immediate, 2),
+        /* 0xEA */ op("NOP", CPU::nop, Implicit, 2),
+        /* 0xEB */ unofficial("SBC", CPU::sbc, Immediate, 2),
+        /* 0xEC */ op("CPX", CPU::cpx, Absolute, 4),
+        /* 0xED */ op("SBC", CPU::sbc, Absolute, 4),
+        /* 0xEE */ op("INC", CPU::inc, Absolute, 6),
+        /* 0xEF */ unofficial("ISB", CPU::isc, Absolute, 6),
+        /* 0xF0 */ op("BEQ", CPU::beq, Relative, 2),
+        /* 0xF1 */ op("SBC", CPU::sbc, IndirectIndexed, 5),
+        /* 0xF2 */ unofficial("JAM", CPU::jam, Implicit, 2),
+        /* 0xF3 */ unofficial("ISB", CPU::isc, IndirectIndexed, 8),
+        /* 0xF4 */ unofficial("NOP", CPU::nop, ZeroPageIndexedX, 4),
+        /* 0xF5 */ op("SBC", CPU::sbc, ZeroPageIndexedX, 4),
+        /* 0xF6 */ op("INC", CPU::inc, ZeroPageIndexedX, 6),
+        /* 0xF7 */ unofficial("ISB", CPU::isc, ZeroPageIndexedX, 6),
+        /* 0xF8 */ op("SED", CPU::sed, Implicit, 2),
+        /* 0xF9 */ op("SBC", CPU::sbc, AbsoluteIndexedY, 4),
+        /* 0xFA */ unofficial("NOP", CPU::nop, Implicit, 2),
+        /* 0xFB */ unofficial("ISB", CPU::isc, AbsoluteIndexedY, 7),
+        /* 0xFC */ unofficial("NOP", CPU::nop, AbsoluteIndexedX, 4),
+        /* 0xFD */ op("SBC", CPU::sbc, AbsoluteIndexedX, 4),
+        /* 0xFE */ op("INC", CPU::inc, AbsoluteIndexedX, 7),
+        /* 0xFF */ unofficial("ISB", CPU::isc, AbsoluteIndexedX, 7),
+    ]
+};
@@ -0,0 +1,5 @@
+include!("chip.rs");
+include!("map_instructions.rs");
+include!("instructions.rs");
+include!("disassembler.rs");
+include!("trace.rs");
@@ -0,0 +1,119 @@
+impl CPU {
+    /// Linha de trace da instrução em PC no formato do nestest.log (Nintendulator):
+    ///
+    /// `C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 21 CYC:7`
+    ///
+    /// Os operandos de memória mostram o endereço efetivo e o valor antes da execução
+    /// (`STX $00 = 00`, `LDA ($80,X) @ 80 = 0200 = 5A`). Só usa `BUS::peek`.
+    pub fn trace_line(&self) -> String {
+        let pc = self.registers.program_counter;
+        let info = &OPCODES[self.bus.peek(pc) as usize];
+        let raw = (0..info.bytes as u16)
+            .map(|i| format!("{:02X}", self.bus.peek(pc.wrapping_add(i))))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let prefix = if info.official { ' ' } else { '*' };
+        let operand = self.trace_operand(pc, info);
+        let text = if operand.is_empty() {
+            format!("{}{}", prefix, info.mnemonic)
+        } else {
+            format!("{}{} {}", prefix, info.mnemonic, operand)
+        };
+
+        format!(
+            "{:04X}  {:<8} {:<33}A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} PPU:{:>3},{:>3} CYC:{}",
+            pc,
+            raw,
+            text,
+            self.registers.acc,
+            self.registers.index_x,
+            self.registers.index_y,
+            self.registers.status_register,
+            self.registers.stack_pointer,
+            self.bus.ppu.scanline,
+            self.bus.ppu.cycle,
+            self.cycles
+        )
+    }
+
+    fn trace_operand(&self, pc: u16, info: &OpcodeInfo) -> String {
+        let low = self.trace_peek(pc.wrapping_add(1));
+        let high = self.trace_peek(pc.wrapping_add(2));
+        let word = self.turn_in_u16(low, high);
+        let x = self.registers.index_x;
+        let y = self.registers.index_y;
+
+        match info.mode {
+            AddressingModes::Implicit => String::new(),
+            AddressingModes::Accumulator => "A".to_string(),
+            AddressingModes::Immediate => format!("#${:02X}", low),
+            AddressingModes::ZeroPage => format!("${:02X} = {:02X}", low, self.trace_peek(low as u16)),
+            AddressingModes::ZeroPageIndexedX => {
+                let address = low.wrapping_add(x);
+                format!("${:02X},X @ {:02X} = {:02X}", low, address, self.trace_peek(address as u16))
+            }
+            AddressingModes::ZeroPageIndexedY => {
+                let address = low.wrapping_add(y);
+                format!("${:02X},Y @ {:02X} = {:02X}", low, address, self.trace_peek(address as u16))
+            }
+            // JMP e JSR não leem o destino
+            AddressingModes::Absolute if matches!(info.mnemonic, "JMP" | "JSR") =>
+                format!("${:04X}", word),
+            AddressingModes::Absolute => format!("${:04X} = {:02X}", word, self.trace_peek(word)),
+            AddressingModes::AbsoluteIndexedX => {
+                let address = word.wrapping_add(x as u16);
+                format!("${:04X},X @ {:04X} = {:02X}", word, address, self.trace_peek(address))
+            }
+            AddressingModes::AbsoluteIndexedY => {
+                let address = word.wrapping_add(y as u16);
+                format!("${:04X},Y @ {:04X} = {:02X}", word, address, self.trace_peek(address))
+            }
+            AddressingModes::IndexedIndirect => {
+                let pointer = low.wrapping_add(x);
+                let address = self.peek_zero_page_u16(pointer);
+                format!(
+                    "(${:02X},X) @ {:02X} = {:04X} = {:02X}",
+                    low,
+                    pointer,
+                    address,
+                    self.trace_peek(address)
+                )
+            }
+            AddressingModes::IndirectIndexed => {
+                let base = self.peek_zero_page_u16(low);
+                let address = base.wrapping_add(y as u16);
+                format!(
+                    "(${:02X}),Y = {:04X} @ {:04X} = {:02X}",
+                    low,
+                    base,
+                    address,
+                    self.trace_peek(address)
+                )
+            }
+            AddressingModes::Indirect => {
+                // Como o Nintendulator, mostra o ponteiro lido sem o bug de página do NMOS
+                // (`JMP ($02FF) = A900`), embora a CPU salte para o destino com o bug
+                let high = self.trace_peek(word.wrapping_add(1));
+                let target = self.turn_in_u16(self.trace_peek(word), high);
+                format!("(${:04X}) = {:04X}", word, target)
+            }
+            AddressingModes::Relative => {
+                let target = pc.wrapping_add(2).wrapping_add(low as i8 as u16);
+                format!("${:04X}", target)
+            }
+        }
+    }
+
+    fn peek_zero_page_u16(&self, pointer: u8) -> u16 {
+        self.turn_in_u16(self.trace_peek(pointer as u16), self.trace_peek(pointer.wrapping_add(1) as u16))
+    }
+
+    /// Registradores de I/O não são lidos pelo trace (a leitura tem efeitos colaterais);
+    /// como o Nintendulator, mostra $FF.
+    fn trace_peek(&self, address: u16) -> u8 {
+        match address {
+            0x2000..=0x401F => 0xFF,
+            _ => self.bus.peek(address),
+        }
+    }
+}
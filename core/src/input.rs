@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Controller {
+    pub a: bool,
+    pub b: bool,
+    pub select: bool,
+    pub start: bool,
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    strobe: bool,
+    shift_register: u8,
+}
+
+impl Controller {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Atualiza o estado de um botão com base na entrada.
+    /// `pressed` indica se a tecla foi pressionada (true) ou liberada (false).
+    pub fn update(&mut self, key: Key, pressed: bool) {
+        use Key::*;
+        match key {
+            A => self.a = pressed,
+            B => self.b = pressed,
+            Select => self.select = pressed,
+            Start => self.start = pressed,
+            Up => self.up = pressed,
+            Down => self.down = pressed,
+            Left => self.left = pressed,
+            Right => self.right = pressed,
+        }
+    }
+
+    /// Escrita em $4016: com o bit 0 em 1 o estado dos botões fica sendo recarregado
+    /// continuamente; ao voltar para 0 o último estado fica travado no shift register.
+    pub fn write(&mut self, value: u8) {
+        self.strobe = (value & 1) == 1;
+        if self.strobe {
+            self.shift_register = self.buttons();
+        }
+    }
+
+    /// Leitura serial de $4016/$4017: um botão por acesso, na ordem
+    /// A, B, Select, Start, Up, Down, Left, Right. Depois de 8 leituras retorna 1.
+    /// Os bits altos vêm do open bus (normalmente $40).
+    pub fn read(&mut self) -> u8 {
+        if self.strobe {
+            return 0x40 | (self.a as u8);
+        }
+        let bit = self.shift_register & 1;
+        self.shift_register = (self.shift_register >> 1) | 0x80;
+        0x40 | bit
+    }
+
+    fn buttons(&self) -> u8 {
+        (self.a as u8)
+            | ((self.b as u8) << 1)
+            | ((self.select as u8) << 2)
+            | ((self.start as u8) << 3)
+            | ((self.up as u8) << 4)
+            | ((self.down as u8) << 5)
+            | ((self.left as u8) << 6)
+            | ((self.right as u8) << 7)
+    }
+}
+
+/// Enumeração dos botões do controle do NES.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    A,
+    B,
+    Select,
+    Start,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Key {
+    /// Converte o nome de um botão ("A", "B", "Select", "Start", "Up", ...) no `Key` correspondente.
+    pub fn from_name(name: &str) -> Option<Key> {
+        match name {
+            "A" => Some(Key::A),
+            "B" => Some(Key::B),
+            "Select" => Some(Key::Select),
+            "Start" => Some(Key::Start),
+            "Up" => Some(Key::Up),
+            "Down" => Some(Key::Down),
+            "Left" => Some(Key::Left),
+            "Right" => Some(Key::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Mapeamento de teclas do teclado (valores de `KeyboardEvent.key`) para botões de um controle.
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    bindings: HashMap<String, Key>,
+}
+
+impl KeyMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mapeamento padrão do jogador 1: Z/X, Control/Enter e setas.
+    pub fn player_one() -> Self {
+        let mut map = Self::new();
+        map.bind("z", Key::A);
+        map.bind("x", Key::B);
+        map.bind("Control", Key::Select);
+        map.bind("Enter", Key::Start);
+        map.bind("ArrowUp", Key::Up);
+        map.bind("ArrowDown", Key::Down);
+        map.bind("ArrowLeft", Key::Left);
+        map.bind("ArrowRight", Key::Right);
+        map
+    }
+
+    /// Mapeamento padrão do jogador 2: K/J, U/I e WASD.
+    pub fn player_two() -> Self {
+        let mut map = Self::new();
+        map.bind("k", Key::A);
+        map.bind("j", Key::B);
+        map.bind("u", Key::Select);
+        map.bind("i", Key::Start);
+        map.bind("w", Key::Up);
+        map.bind("s", Key::Down);
+        map.bind("a", Key::Left);
+        map.bind("d", Key::Right);
+        map
+    }
+
+    pub fn bind(&mut self, key: &str, button: Key) {
+        self.bindings.insert(key.to_string(), button);
+    }
+
+    pub fn unbind(&mut self, key: &str) {
+        self.bindings.remove(key);
+    }
+
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+    }
+
+    pub fn get(&self, key: &str) -> Option<Key> {
+        self.bindings.get(key).copied()
+    }
+}
\ No newline at end of file
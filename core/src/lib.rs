@@ -0,0 +1,16 @@
+//! Núcleo do emulador, sem dependências de plataforma: CPU, PPU, APU, barramento e
+//! cartucho. O frontend wasm (`web_nes`) e as ferramentas nativas usam este crate.
+
+#[macro_use]
+pub mod logging;
+pub mod cpu;
+pub mod rom;
+pub mod input;
+pub mod ppu;
+pub mod apu;
+pub mod mapper;
+pub mod bus;
+pub mod nes;
+pub mod test_rom;
+
+pub use crate::nes::Nes;
@@ -0,0 +1,207 @@
+//! Facade de log do emulador: níveis, categorias por subsistema e sinks plugáveis.
+//!
+//! Tudo começa desligado. `set_level` habilita uma categoria; as mensagens vão para os
+//! sinks registrados (por padrão stderr; o frontend wasm troca pelo console do navegador)
+//! e para um ring buffer que o debugger pode consultar.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn from_name(name: &str) -> Option<Level> {
+        match name.to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    Cpu,
+    Ppu,
+    Apu,
+    Mapper,
+    Rom,
+    Frontend,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::Cpu,
+        Category::Ppu,
+        Category::Apu,
+        Category::Mapper,
+        Category::Rom,
+        Category::Frontend,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Category> {
+        match name.to_ascii_lowercase().as_str() {
+            "cpu" => Some(Category::Cpu),
+            "ppu" => Some(Category::Ppu),
+            "apu" => Some(Category::Apu),
+            "mapper" => Some(Category::Mapper),
+            "rom" => Some(Category::Rom),
+            "frontend" => Some(Category::Frontend),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Category::Cpu => "cpu",
+            Category::Ppu => "ppu",
+            Category::Apu => "apu",
+            Category::Mapper => "mapper",
+            Category::Rom => "rom",
+            Category::Frontend => "frontend",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub level: Level,
+    pub category: Category,
+    pub message: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.category.name(), self.level.name(), self.message)
+    }
+}
+
+/// Destino das mensagens de log.
+pub trait LogSink {
+    fn log(&mut self, entry: &LogEntry);
+}
+
+/// Sink padrão: escreve em stderr.
+pub struct StderrSink;
+
+impl LogSink for StderrSink {
+    fn log(&mut self, entry: &LogEntry) {
+        eprintln!("{}", entry);
+    }
+}
+
+const DEFAULT_RING_CAPACITY: usize = 512;
+
+struct Logger {
+    levels: [Option<Level>; Category::ALL.len()],
+    sinks: Vec<Box<dyn LogSink>>,
+    ring_buffer: VecDeque<LogEntry>,
+    ring_capacity: usize,
+}
+
+thread_local! {
+    static LOGGER: RefCell<Logger> = RefCell::new(Logger {
+        levels: [None; Category::ALL.len()],
+        sinks: vec![Box::new(StderrSink)],
+        ring_buffer: VecDeque::new(),
+        ring_capacity: DEFAULT_RING_CAPACITY,
+    });
+}
+
+/// Nível máximo registrado para `category`; `None` desliga a categoria.
+pub fn set_level(category: Category, level: Option<Level>) {
+    LOGGER.with(|logger| {
+        logger.borrow_mut().levels[category as usize] = level;
+    });
+}
+
+pub fn set_all_levels(level: Option<Level>) {
+    LOGGER.with(|logger| {
+        logger.borrow_mut().levels = [level; Category::ALL.len()];
+    });
+}
+
+pub fn enabled(category: Category, level: Level) -> bool {
+    LOGGER.with(|logger| {
+        logger.borrow().levels[category as usize].is_some_and(|max| level <= max)
+    })
+}
+
+pub fn add_sink(sink: Box<dyn LogSink>) {
+    LOGGER.with(|logger| logger.borrow_mut().sinks.push(sink));
+}
+
+/// Remove todos os sinks, inclusive o StderrSink (o ring buffer continua ativo).
+pub fn clear_sinks() {
+    LOGGER.with(|logger| logger.borrow_mut().sinks.clear());
+}
+
+/// Tamanho do ring buffer do debugger; 0 desliga.
+pub fn set_ring_buffer_capacity(capacity: usize) {
+    LOGGER.with(|logger| {
+        let mut logger = logger.borrow_mut();
+        logger.ring_capacity = capacity;
+        while logger.ring_buffer.len() > capacity {
+            logger.ring_buffer.pop_front();
+        }
+    });
+}
+
+/// Retira e retorna as entradas acumuladas no ring buffer.
+pub fn take_ring_buffer() -> Vec<LogEntry> {
+    LOGGER.with(|logger| logger.borrow_mut().ring_buffer.drain(..).collect())
+}
+
+/// Usado pela macro `log!`, que já checou `enabled` antes de formatar a mensagem.
+pub fn log(category: Category, level: Level, args: fmt::Arguments<'_>) {
+    let entry = LogEntry {
+        level,
+        category,
+        message: args.to_string(),
+    };
+    LOGGER.with(|logger| {
+        let mut logger = logger.borrow_mut();
+        for sink in logger.sinks.iter_mut() {
+            sink.log(&entry);
+        }
+        if logger.ring_capacity > 0 {
+            if logger.ring_buffer.len() == logger.ring_capacity {
+                logger.ring_buffer.pop_front();
+            }
+            logger.ring_buffer.push_back(entry);
+        }
+    });
+}
+
+/// `log!(Category::Mapper, Level::Warn, "PRG read out of bounds: {:04X}", address)`
+///
+/// A mensagem só é formatada se a categoria estiver habilitada nesse nível.
+#[macro_export]
+macro_rules! log {
+    ($category:expr, $level:expr, $($arg:tt)+) => {
+        if $crate::logging::enabled($category, $level) {
+            $crate::logging::log($category, $level, format_args!($($arg)+));
+        }
+    };
+}
@@ -0,0 +1,1217 @@
+use std::cell::RefCell;
+use std::io::{ Error, ErrorKind };
+use std::rc::Rc;
+use crate::logging::{ Category, Level };
+use crate::rom::Mirroring;
+
+/// Tamanho da janela da PRG-RAM (work RAM) em $6000-$7FFF.
+pub const PRG_RAM_SIZE: usize = 0x2000;
+
+/// PRG-RAM do cartucho. Com bateria, é o save do jogo; `dirty` indica que mudou desde o
+/// último `take_dirty`, para o frontend saber quando gravar.
+pub struct PrgRam {
+    data: Vec<u8>,
+    dirty: bool,
+}
+
+impl PrgRam {
+    pub fn new(size: usize) -> Self {
+        Self {
+            data: vec![0; size],
+            dirty: false,
+        }
+    }
+
+    /// Lê pela janela $6000-$7FFF; RAMs menores que 8 KB são espelhadas.
+    pub fn read(&self, address: u16) -> u8 {
+        self.read_offset((address as usize) - 0x6000)
+    }
+
+    pub fn write(&mut self, address: u16, value: u8) {
+        self.write_offset((address as usize) - 0x6000, value);
+    }
+
+    /// Acesso por posição na RAM inteira, para mappers que chaveiam bancos de PRG-RAM.
+    pub fn read_offset(&self, offset: usize) -> u8 {
+        if self.data.is_empty() {
+            return 0;
+        }
+        self.data[offset % self.data.len()]
+    }
+
+    pub fn write_offset(&mut self, offset: usize, value: u8) {
+        if self.data.is_empty() {
+            return;
+        }
+        let index = offset % self.data.len();
+        if self.data[index] != value {
+            self.data[index] = value;
+            self.dirty = true;
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Restaura um save; o tamanho precisa ser o da PRG-RAM do cartucho.
+    pub fn load(&mut self, data: &[u8]) -> Result<(), Error> {
+        if data.len() != self.data.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Save size mismatch. Expected {}, got {}", self.data.len(), data.len())
+            ));
+        }
+        self.data.copy_from_slice(data);
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Indica (uma vez) que a RAM mudou desde a última chamada.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+}
+
+/// Fase do render em que a PPU fez um acesso.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PpuFetch {
+    /// Nametable, atributo e padrões dos tiles de background.
+    Background,
+    /// Padrões dos sprites.
+    Sprite,
+    /// Leitura ou escrita da CPU por $2007.
+    Data,
+}
+
+/// Um acesso da PPU ao barramento de vídeo, como o cartucho o vê.
+#[derive(Clone, Copy, Debug)]
+pub struct PpuAccess {
+    pub address: u16,
+    pub fetch: PpuFetch,
+    pub scanline: u16,
+    pub cycle: u16,
+    /// Ciclos de PPU desde o power-up (sem descontar o ciclo pulado nos frames ímpares).
+    pub dot: u64,
+}
+
+/// Destino de um acesso de nametable.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Nametable {
+    /// Página de 1 KB da VRAM do console (2 e 3 só existem em placas four-screen).
+    Ciram(u16),
+    /// O cartucho responde (ExRAM e fill mode do MMC5).
+    Cartridge,
+}
+
+/// Handle do cartucho compartilhado entre a CPU (via BUS) e a PPU.
+pub type SharedMapper = Rc<RefCell<dyn Mapper>>;
+
+pub trait Mapper {
+    /// Leitura sem efeitos colaterais (usada também por `BUS::peek`, trace e disassembler).
+    fn read_prg(&self, address: u16) -> u8;
+    /// Leitura feita pela CPU no barramento, para registradores que mudam ao serem lidos
+    /// (ex.: $5204 do MMC5 reconhece o IRQ).
+    fn cpu_read_prg(&mut self, address: u16) -> u8 {
+        self.read_prg(address)
+    }
+    fn write_prg(&mut self, address: u16, value: u8);
+    fn read_chr(&self, address: u16) -> u8;
+    fn write_chr(&mut self, address: u16, value: u8);
+    fn get_mirroring(&self) -> Mirroring;
+    /// Linha de IRQ do cartucho (ex.: contador de scanlines do MMC3).
+    fn irq_pending(&self) -> bool {
+        false
+    }
+    /// Acesso da PPU ao barramento de vídeo (fetches do render e $2007), chamado antes da
+    /// leitura ou escrita. Permite ao cartucho observar a linha A12 (MMC3) ou trocar
+    /// bancos conforme a fase do fetch (MMC5).
+    fn ppu_access(&mut self, _access: PpuAccess) {}
+    /// Escrita da CPU em um registrador da PPU ($2000-$2007), que alguns cartuchos espiam.
+    fn ppu_register_write(&mut self, _address: u16, _value: u8) {}
+    /// Para onde vai um acesso de nametable ($2000-$2FFF). O padrão segue `get_mirroring`.
+    fn nametable(&self, address: u16) -> Nametable {
+        Nametable::Ciram(self.get_mirroring().nametable_page((address >> 10) & 0x3))
+    }
+    /// Nametables respondidas pelo cartucho (`Nametable::Cartridge`).
+    fn read_nametable(&self, _address: u16) -> u8 {
+        0
+    }
+    fn write_nametable(&mut self, _address: u16, _value: u8) {}
+    /// PRG-RAM em $6000-$7FFF, se o cartucho tiver.
+    fn prg_ram(&self) -> Option<&PrgRam> {
+        None
+    }
+    fn prg_ram_mut(&mut self) -> Option<&mut PrgRam> {
+        None
+    }
+}
+
+pub struct Mapper0 {
+    prg_rom: Vec<u8>,
+    chr_rom: Vec<u8>,
+    prg_ram: PrgRam,
+    prg_banks: u8,
+    chr_banks: u8,
+    mirroring: Mirroring,
+}
+
+impl Mapper0 {
+    pub fn new(
+        prg_rom: Vec<u8>,
+        chr_rom: Vec<u8>,
+        prg_banks: u8,
+        chr_banks: u8,
+        mirroring: Mirroring,
+        prg_ram_size: usize
+    ) -> Self {
+        Self {
+            prg_rom,
+            chr_rom,
+            prg_ram: PrgRam::new(prg_ram_size),
+            prg_banks,
+            chr_banks,
+            mirroring,
+        }
+    }
+}
+
+impl Mapper for Mapper0 {
+    fn read_prg(&self, address: u16) -> u8 {
+        match address {
+            0x6000..=0x7FFF => self.prg_ram.read(address),
+            // NROM-128 (16 KB) espelha o banco em $C000; NROM-256 ocupa $8000-$FFFF
+            0x8000..=0xFFFF => self.prg_rom[(address as usize - 0x8000) % self.prg_rom.len()],
+            _ => 0, // Endereços fora da faixa do PRG ROM
+        }
+    }
+
+    fn write_prg(&mut self, address: u16, value: u8) {
+        // NROM não tem registradores; só a PRG-RAM aceita escrita
+        if let 0x6000..=0x7FFF = address {
+            self.prg_ram.write(address, value);
+        }
+    }
+
+    fn read_chr(&self, address: u16) -> u8 {
+        self.chr_rom[(address & 0x1fff) as usize]
+    }
+
+    fn write_chr(&mut self, address: u16, value: u8) {
+        // Only boards without CHR-ROM (chr_banks == 0) have writable CHR-RAM
+        if self.chr_banks == 0 {
+            self.chr_rom[(address & 0x1fff) as usize] = value;
+        }
+    }
+
+    fn get_mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn prg_ram(&self) -> Option<&PrgRam> {
+        Some(&self.prg_ram)
+    }
+
+    fn prg_ram_mut(&mut self) -> Option<&mut PrgRam> {
+        Some(&mut self.prg_ram)
+    }
+}
+
+pub struct Mapper1 {
+    prg_rom: Vec<u8>,
+    chr_rom: Vec<u8>,
+    prg_ram: PrgRam,
+    prg_ram_enabled: bool, // Bit 4 do registrador de PRG bank (0 = habilitada)
+    shift_register: u8,
+    control: u8,
+    chr_bank_0: u8,
+    chr_bank_1: u8,
+    prg_bank: u8,
+    prg_banks: u8,
+    chr_banks: u8,
+    shift_count: u8,
+}
+
+impl Mapper1 {
+    pub fn new(
+        prg_rom: Vec<u8>,
+        chr_rom: Vec<u8>,
+        prg_banks: u8,
+        chr_banks: u8,
+        prg_ram_size: usize
+    ) -> Self {
+        Self {
+            prg_rom,
+            chr_rom,
+            prg_ram: PrgRam::new(prg_ram_size),
+            prg_ram_enabled: true,
+            shift_register: 0x10,
+            control: 0x0c,
+            chr_bank_0: 0,
+            chr_bank_1: 0,
+            prg_bank: 0,
+            prg_banks,
+            chr_banks,
+            shift_count: 0,
+        }
+    }
+}
+
+impl Mapper for Mapper1 {
+    fn read_prg(&self, address: u16) -> u8 {
+        if address < 0x8000 {
+            return if self.prg_ram_enabled && address >= 0x6000 {
+                self.prg_ram.read(address)
+            } else {
+                0
+            };
+        }
+
+        match (self.control >> 2) & 0x3 {
+            0 | 1 => {
+                // 32KB switching
+                let bank = (self.prg_bank & 0xfe) as usize;
+                let offset = (address & 0x7fff) as usize;
+                let addr = bank * 0x8000 + offset;
+                if addr < self.prg_rom.len() {
+                    self.prg_rom[addr]
+                } else {
+                    log!(
+                        Category::Mapper,
+                        Level::Warn,
+                        "PRG Read Out of Bounds (32KB Mode): Address {:04X}, Index {}",
+                        address,
+                        addr
+                    );
+                    0
+                }
+            }
+            2 => {
+                // fix first bank ($8000-$BFFF), switch second ($C000-$FFFF)
+                if address < 0xc000 {
+                    let addr = (address & 0x3fff) as usize;
+                    if addr < self.prg_rom.len() {
+                        self.prg_rom[addr]
+                    } else {
+                        log!(
+                            Category::Mapper,
+                            Level::Warn,
+                            "PRG Read Out of Bounds (Fixed First): Address {:04X}, Index {}",
+                            address,
+                            addr
+                        );
+                        0
+                    }
+                } else {
+                    let bank = (self.prg_bank & 0x0f) as usize;
+                    let offset = (address & 0x3fff) as usize;
+                    let addr = bank * 0x4000 + offset;
+                    if addr < self.prg_rom.len() {
+                        self.prg_rom[addr]
+                    } else {
+                        log!(
+                            Category::Mapper,
+                            Level::Warn,
+                            "PRG Read Out of Bounds (Switched Second): Address {:04X}, Index {}",
+                            address,
+                            addr
+                        );
+                        0
+                    }
+                }
+            }
+            3 => {
+                // fix last bank ($C000-$FFFF), switch first ($8000-$BFFF)
+                if address >= 0xc000 {
+                    let offset = (address & 0x3fff) as usize;
+                    let last_bank_start = ((self.prg_banks as usize) - 1) * 0x4000;
+                    let addr = last_bank_start + offset;
+                    if addr < self.prg_rom.len() {
+                        self.prg_rom[addr]
+                    } else {
+                        log!(
+                            Category::Mapper,
+                            Level::Warn,
+                            "PRG Read Out of Bounds (Fixed Last): Address {:04X}, Index {}",
+                            address,
+                            addr
+                        );
+                        0
+                    }
+                } else {
+                    let bank = (self.prg_bank & 0x0f) as usize;
+                    let offset = (address & 0x3fff) as usize;
+                    let addr = bank * 0x4000 + offset;
+                    if addr < self.prg_rom.len() {
+                        self.prg_rom[addr]
+                    } else {
+                        log!(
+                            Category::Mapper,
+                            Level::Warn,
+                            "PRG Read Out of Bounds (Switched First): Address {:04X}, Index {}",
+                            address,
+                            addr
+                        );
+                        0
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn write_prg(&mut self, address: u16, value: u8) {
+        if address < 0x8000 {
+            if self.prg_ram_enabled && address >= 0x6000 {
+                self.prg_ram.write(address, value);
+            }
+            return;
+        }
+
+        // Reset do registrador de deslocamento se bit 7 está setado
+        if (value & 0x80) != 0 {
+            self.shift_register = 0x10;
+            self.shift_count = 0; // Também resetar o contador
+            self.control |= 0x0c;
+            return;
+        }
+
+        // Deslocamento e adição do novo bit
+        self.shift_register >>= 1;
+        self.shift_register |= (value & 1) << 4;
+        self.shift_count += 1;
+
+        // Quando acumular 5 bits, processar o registro
+        if self.shift_count == 5 {
+            let target_register = (address >> 13) & 0x3;
+            let value = self.shift_register & 0x1f;
+
+            match target_register {
+                0 => {
+                    // Control
+                    self.control = value;
+                    // Atualizar espelhamento imediatamente
+                }
+                1 => {
+                    // CHR bank 0
+                    self.chr_bank_0 = value;
+                }
+                2 => {
+                    // CHR bank 1
+                    self.chr_bank_1 = value;
+                }
+                3 => {
+                    // PRG bank
+                    self.prg_bank = value & 0x0f; // Máscara para 4 bits
+                    self.prg_ram_enabled = (value & 0x10) == 0;
+                }
+                _ => unreachable!(),
+            }
+
+            // Reset após processamento
+            self.shift_register = 0x10;
+            self.shift_count = 0;
+        }
+    }
+
+    fn read_chr(&self, address: u16) -> u8 {
+        let addr = if (self.control & 0x10) == 0 {
+            // 8KB mode
+            (((self.chr_bank_0 as usize) & 0x1e) * 0x1000 + ((address as usize) & 0x1fff)) %
+                self.chr_rom.len()
+        } else {
+            // 4KB mode
+            if address <= 0x0fff {
+                ((self.chr_bank_0 as usize) * 0x1000 + ((address as usize) & 0x0fff)) %
+                    self.chr_rom.len()
+            } else {
+                ((self.chr_bank_1 as usize) * 0x1000 + ((address as usize) & 0x0fff)) %
+                    self.chr_rom.len()
+            }
+        };
+        self.chr_rom[addr]
+    }
+
+    fn write_chr(&mut self, address: u16, value: u8) {
+        let addr = if (self.control & 0x10) == 0 {
+            (((self.chr_bank_0 as usize) & 0x1e) * 0x1000 + ((address as usize) & 0x1fff)) %
+                self.chr_rom.len()
+        } else {
+            if address <= 0x0fff {
+                ((self.chr_bank_0 as usize) * 0x1000 + ((address as usize) & 0x0fff)) %
+                    self.chr_rom.len()
+            } else {
+                ((self.chr_bank_1 as usize) * 0x1000 + ((address as usize) & 0x0fff)) %
+                    self.chr_rom.len()
+            }
+        };
+        self.chr_rom[addr] = value;
+    }
+
+    fn get_mirroring(&self) -> Mirroring {
+        match self.control & 0x3 {
+            0 => Mirroring::OneScreenLo,
+            1 => Mirroring::OneScreenHi,
+            2 => Mirroring::Vertical,
+            3 => Mirroring::Horizontal,
+            _ => unreachable!(),
+        }
+    }
+
+    fn prg_ram(&self) -> Option<&PrgRam> {
+        Some(&self.prg_ram)
+    }
+
+    fn prg_ram_mut(&mut self) -> Option<&mut PrgRam> {
+        Some(&mut self.prg_ram)
+    }
+}
+
+/// UxROM: banco de 16 KB selecionável em $8000-$BFFF e o último banco fixo em
+/// $C000-$FFFF. Os cartuchos usam 8 KB de CHR-RAM e espelhamento fixo.
+pub struct Mapper2 {
+    prg_rom: Vec<u8>,
+    chr_rom: Vec<u8>,
+    prg_bank: u8,
+    prg_banks: u8,
+    chr_banks: u8,
+    mirroring: Mirroring,
+}
+
+impl Mapper2 {
+    pub fn new(
+        prg_rom: Vec<u8>,
+        chr_rom: Vec<u8>,
+        prg_banks: u8,
+        chr_banks: u8,
+        mirroring: Mirroring
+    ) -> Self {
+        Self {
+            prg_rom,
+            chr_rom,
+            prg_bank: 0,
+            prg_banks,
+            chr_banks,
+            mirroring,
+        }
+    }
+}
+
+impl Mapper for Mapper2 {
+    fn read_prg(&self, address: u16) -> u8 {
+        let bank = match address {
+            0x8000..=0xBFFF => self.prg_bank % self.prg_banks,
+            0xC000..=0xFFFF => self.prg_banks - 1,
+            _ => {
+                return 0;
+            }
+        };
+        self.prg_rom[(bank as usize) * 0x4000 + ((address & 0x3fff) as usize)]
+    }
+
+    fn write_prg(&mut self, address: u16, value: u8) {
+        // Qualquer escrita em $8000-$FFFF seleciona o banco (UNROM usa 3 bits, UOROM 4)
+        if address >= 0x8000 {
+            self.prg_bank = value & 0x0f;
+        }
+    }
+
+    fn read_chr(&self, address: u16) -> u8 {
+        self.chr_rom[(address & 0x1fff) as usize]
+    }
+
+    fn write_chr(&mut self, address: u16, value: u8) {
+        if self.chr_banks == 0 {
+            self.chr_rom[(address & 0x1fff) as usize] = value;
+        }
+    }
+
+    fn get_mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+}
+
+/// CNROM: PRG fixo (16 ou 32 KB, como o NROM) e banco de 8 KB de CHR-ROM selecionado
+/// por escritas em $8000-$FFFF.
+pub struct Mapper3 {
+    prg_rom: Vec<u8>,
+    chr_rom: Vec<u8>,
+    chr_bank: u8,
+    chr_banks: u8,
+    mirroring: Mirroring,
+    // A ROM também dirige o barramento na escrita: o valor efetivo é o AND dos dois
+    bus_conflicts: bool,
+}
+
+impl Mapper3 {
+    pub fn new(
+        prg_rom: Vec<u8>,
+        chr_rom: Vec<u8>,
+        chr_banks: u8,
+        mirroring: Mirroring,
+        bus_conflicts: bool
+    ) -> Self {
+        Self {
+            prg_rom,
+            chr_rom,
+            chr_bank: 0,
+            chr_banks: chr_banks.max(1),
+            mirroring,
+            bus_conflicts,
+        }
+    }
+}
+
+impl Mapper for Mapper3 {
+    fn read_prg(&self, address: u16) -> u8 {
+        match address {
+            0x8000..=0xFFFF => self.prg_rom[(address as usize - 0x8000) % self.prg_rom.len()],
+            _ => 0,
+        }
+    }
+
+    fn write_prg(&mut self, address: u16, value: u8) {
+        if address < 0x8000 {
+            return;
+        }
+        let value = if self.bus_conflicts { value & self.read_prg(address) } else { value };
+        self.chr_bank = value % self.chr_banks;
+    }
+
+    fn read_chr(&self, address: u16) -> u8 {
+        self.chr_rom[(self.chr_bank as usize) * 0x2000 + ((address & 0x1fff) as usize)]
+    }
+
+    fn write_chr(&mut self, _address: u16, _value: u8) {
+        // CHR-ROM
+    }
+
+    fn get_mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+}
+
+/// AxROM: PRG em um único banco de 32 KB selecionável e CHR-RAM. A mesma escrita em
+/// $8000-$FFFF escolhe qual nametable interna é usada nas quatro (espelhamento de uma tela).
+pub struct Mapper7 {
+    prg_rom: Vec<u8>,
+    chr_rom: Vec<u8>,
+    prg_bank: u8,
+    chr_banks: u8,
+    one_screen_hi: bool,
+    bus_conflicts: bool, // AMROM; ANROM/AOROM não têm
+}
+
+impl Mapper7 {
+    pub fn new(prg_rom: Vec<u8>, chr_rom: Vec<u8>, chr_banks: u8, bus_conflicts: bool) -> Self {
+        Self {
+            prg_rom,
+            chr_rom,
+            prg_bank: 0,
+            chr_banks,
+            one_screen_hi: false,
+            bus_conflicts,
+        }
+    }
+}
+
+impl Mapper for Mapper7 {
+    fn read_prg(&self, address: u16) -> u8 {
+        match address {
+            0x8000..=0xFFFF => {
+                let offset = (self.prg_bank as usize) * 0x8000 + ((address & 0x7fff) as usize);
+                self.prg_rom[offset % self.prg_rom.len()]
+            }
+            _ => 0,
+        }
+    }
+
+    fn write_prg(&mut self, address: u16, value: u8) {
+        if address < 0x8000 {
+            return;
+        }
+        let value = if self.bus_conflicts { value & self.read_prg(address) } else { value };
+        self.prg_bank = value & 0x07;
+        self.one_screen_hi = (value & 0x10) != 0;
+    }
+
+    fn read_chr(&self, address: u16) -> u8 {
+        self.chr_rom[(address & 0x1fff) as usize]
+    }
+
+    fn write_chr(&mut self, address: u16, value: u8) {
+        if self.chr_banks == 0 {
+            self.chr_rom[(address & 0x1fff) as usize] = value;
+        }
+    }
+
+    fn get_mirroring(&self) -> Mirroring {
+        if self.one_screen_hi { Mirroring::OneScreenHi } else { Mirroring::OneScreenLo }
+    }
+}
+
+/// Ciclos de PPU que A12 precisa ficar baixa para o MMC3 contar a próxima subida.
+const A12_FILTER_DOTS: u64 = 10;
+
+/// MMC3 (TxROM): PRG em bancos de 8 KB e CHR em bancos de 1/2 KB, com modos de inversão,
+/// espelhamento controlado pelo jogo e um contador de scanlines que gera IRQ, clocado
+/// pelas bordas de subida da linha A12 da PPU.
+pub struct Mapper4 {
+    prg_rom: Vec<u8>,
+    chr_rom: Vec<u8>,
+    prg_ram: PrgRam,
+    prg_ram_enabled: bool,
+    prg_ram_write_protect: bool,
+    chr_banks: u8,
+    bank_select: u8,     // $8000: registrador alvo (bits 0-2), modo PRG (6), inversão CHR (7)
+    registers: [u8; 8],  // R0-R5 CHR, R6-R7 PRG
+    mirroring: Mirroring,
+    four_screen: bool,
+    irq_latch: u8,
+    irq_counter: u8,
+    irq_reload: bool,
+    irq_enabled: bool,
+    irq_pending: bool,
+    a12: bool,           // Último nível visto da linha A12
+    a12_low_since: u64,  // Ciclo de PPU em que A12 desceu
+}
+
+impl Mapper4 {
+    pub fn new(
+        prg_rom: Vec<u8>,
+        chr_rom: Vec<u8>,
+        chr_banks: u8,
+        mirroring: Mirroring,
+        prg_ram_size: usize
+    ) -> Self {
+        Self {
+            prg_rom,
+            chr_rom,
+            prg_ram: PrgRam::new(prg_ram_size),
+            prg_ram_enabled: true,
+            prg_ram_write_protect: false,
+            chr_banks,
+            bank_select: 0,
+            registers: [0, 2, 4, 5, 6, 7, 0, 1],
+            mirroring,
+            four_screen: mirroring == Mirroring::FourScreen,
+            irq_latch: 0,
+            irq_counter: 0,
+            irq_reload: false,
+            irq_enabled: false,
+            irq_pending: false,
+            a12: false,
+            a12_low_since: 0,
+        }
+    }
+
+    fn prg_offset(&self, address: u16) -> usize {
+        let banks = self.prg_rom.len() / 0x2000;
+        let second_last = banks - 2;
+        let bank = match ((address >> 13) & 0x3, (self.bank_select & 0x40) != 0) {
+            (0, false) | (2, true) => self.registers[6] as usize,
+            (0, true) | (2, false) => second_last,
+            (1, _) => self.registers[7] as usize,
+            _ => banks - 1,
+        };
+        (bank % banks) * 0x2000 + ((address & 0x1fff) as usize)
+    }
+
+    fn chr_offset(&self, address: u16) -> usize {
+        // A inversão troca as metades $0000 e $1000 da pattern table
+        let address = if (self.bank_select & 0x80) != 0 { address ^ 0x1000 } else { address };
+        let bank = match address & 0x1fff {
+            0x0000..=0x07ff => (self.registers[0] & 0xfe) as usize + ((address >> 10) & 1) as usize,
+            0x0800..=0x0fff => (self.registers[1] & 0xfe) as usize + ((address >> 10) & 1) as usize,
+            _ => self.registers[2 + (((address >> 10) & 0x3) as usize)] as usize,
+        };
+        (bank * 0x400 + ((address & 0x3ff) as usize)) % self.chr_rom.len()
+    }
+
+    fn clock_irq_counter(&mut self) {
+        if self.irq_counter == 0 || self.irq_reload {
+            self.irq_counter = self.irq_latch;
+            self.irq_reload = false;
+        } else {
+            self.irq_counter -= 1;
+        }
+        if self.irq_counter == 0 && self.irq_enabled {
+            self.irq_pending = true;
+        }
+    }
+}
+
+impl Mapper for Mapper4 {
+    fn read_prg(&self, address: u16) -> u8 {
+        match address {
+            0x6000..=0x7FFF if self.prg_ram_enabled => self.prg_ram.read(address),
+            0x8000..=0xFFFF => self.prg_rom[self.prg_offset(address)],
+            _ => 0,
+        }
+    }
+
+    fn write_prg(&mut self, address: u16, value: u8) {
+        let even = (address & 1) == 0;
+        match address {
+            0x6000..=0x7FFF if self.prg_ram_enabled && !self.prg_ram_write_protect => {
+                self.prg_ram.write(address, value);
+            }
+            0x8000..=0x9FFF if even => self.bank_select = value,
+            0x8000..=0x9FFF => self.registers[(self.bank_select & 0x07) as usize] = value,
+            // Placas com VRAM extra ignoram o espelhamento
+            0xA000..=0xBFFF if even && !self.four_screen => {
+                self.mirroring = if (value & 1) == 0 {
+                    Mirroring::Vertical
+                } else {
+                    Mirroring::Horizontal
+                };
+            }
+            0xA000..=0xBFFF if even => {}
+            0xA000..=0xBFFF => {
+                self.prg_ram_enabled = (value & 0x80) != 0;
+                self.prg_ram_write_protect = (value & 0x40) != 0;
+            }
+            0xC000..=0xDFFF if even => self.irq_latch = value,
+            0xC000..=0xDFFF => {
+                self.irq_counter = 0;
+                self.irq_reload = true;
+            }
+            0xE000..=0xFFFF if even => {
+                // Desabilitar também reconhece o IRQ pendente
+                self.irq_enabled = false;
+                self.irq_pending = false;
+            }
+            0xE000..=0xFFFF => self.irq_enabled = true,
+            _ => {}
+        }
+    }
+
+    fn read_chr(&self, address: u16) -> u8 {
+        self.chr_rom[self.chr_offset(address)]
+    }
+
+    fn write_chr(&mut self, address: u16, value: u8) {
+        if self.chr_banks == 0 {
+            let offset = self.chr_offset(address);
+            self.chr_rom[offset] = value;
+        }
+    }
+
+    fn get_mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq_pending
+    }
+
+    fn ppu_access(&mut self, access: PpuAccess) {
+        let a12 = (access.address & 0x1000) != 0;
+        if a12 && !self.a12 {
+            // O chip filtra subidas depois de A12 ficar baixa por menos de ~3 ciclos de
+            // M2; assim o background em $1000 não conta a cada tile
+            if access.dot.wrapping_sub(self.a12_low_since) >= A12_FILTER_DOTS {
+                self.clock_irq_counter();
+            }
+        } else if !a12 && self.a12 {
+            self.a12_low_since = access.dot;
+        }
+        self.a12 = a12;
+    }
+
+    fn prg_ram(&self) -> Option<&PrgRam> {
+        Some(&self.prg_ram)
+    }
+
+    fn prg_ram_mut(&mut self) -> Option<&mut PrgRam> {
+        Some(&mut self.prg_ram)
+    }
+}
+
+/// Tamanho da ExRAM interna do MMC5.
+const EXRAM_SIZE: usize = 0x400;
+
+/// MMC5 (ExROM): PRG em 4 modos (32/16/16+8/8 KB) com ROM ou PRG-RAM em $8000-$DFFF,
+/// CHR em 4 modos (8/4/2/1 KB) com um conjunto de bancos para sprites e outro para o
+/// background com sprites 8x16, 1 KB de ExRAM (nametable extra, atributos estendidos ou
+/// RAM comum), nametables por slot com fill mode, IRQ de scanline, multiplicador e
+/// divisão vertical da tela.
+pub struct Mapper5 {
+    prg_rom: Vec<u8>,
+    chr_rom: Vec<u8>,
+    prg_ram: PrgRam,
+    chr_banks: u8,
+    prg_mode: u8,               // $5100
+    chr_mode: u8,               // $5101
+    prg_ram_protect: [u8; 2],   // $5102/$5103: escrita liberada com %10 e %01
+    exram_mode: u8,             // $5104
+    nametable_mapping: u8,      // $5105: 2 bits por slot
+    fill_tile: u8,              // $5106
+    fill_attribute: u8,         // $5107
+    prg_ram_bank: u8,           // $5113
+    prg_banks: [u8; 4],         // $5114-$5117; bit 7 = ROM
+    chr_banks_a: [u16; 8],      // $5120-$5127: sprites (e tudo com sprites 8x8)
+    chr_banks_b: [u16; 4],      // $5128-$512B: background com sprites 8x16
+    chr_upper: u8,              // $5130: bits 8-9 dos bancos de CHR
+    last_chr_set_b: bool,       // $2007 com sprites 8x16 usa o último conjunto escrito
+    split_control: u8,          // $5200
+    split_scroll: u8,           // $5201
+    split_bank: u8,             // $5202
+    irq_compare: u8,            // $5203
+    irq_enabled: bool,
+    irq_pending: bool,          // Lido (e reconhecido) por $5204
+    in_frame: bool,
+    scanline_counter: u8,
+    multiplicand: u8,           // $5205
+    multiplier: u8,             // $5206
+    exram: [u8; EXRAM_SIZE],
+    tall_sprites: bool,         // PPUCTRL bit 5, espiado na escrita
+    // Estado do fetch em andamento (ver `ppu_access`)
+    fetch: PpuFetch,
+    split_tile: bool,
+    split_y: u16,
+    split_column: u16,
+    ext_attribute: u8,
+}
+
+impl Mapper5 {
+    pub fn new(prg_rom: Vec<u8>, chr_rom: Vec<u8>, chr_banks: u8, prg_ram_size: usize) -> Self {
+        Self {
+            prg_rom,
+            chr_rom,
+            prg_ram: PrgRam::new(prg_ram_size),
+            chr_banks,
+            prg_mode: 3,
+            chr_mode: 3,
+            prg_ram_protect: [0, 0],
+            exram_mode: 0,
+            nametable_mapping: 0,
+            fill_tile: 0,
+            fill_attribute: 0,
+            prg_ram_bank: 0,
+            prg_banks: [0xff; 4],
+            chr_banks_a: [0; 8],
+            chr_banks_b: [0; 4],
+            chr_upper: 0,
+            last_chr_set_b: false,
+            split_control: 0,
+            split_scroll: 0,
+            split_bank: 0,
+            irq_compare: 0,
+            irq_enabled: false,
+            irq_pending: false,
+            in_frame: false,
+            scanline_counter: 0,
+            multiplicand: 0xff,
+            multiplier: 0xff,
+            exram: [0; EXRAM_SIZE],
+            tall_sprites: false,
+            fetch: PpuFetch::Data,
+            split_tile: false,
+            split_y: 0,
+            split_column: 0,
+            ext_attribute: 0,
+        }
+    }
+
+    /// Registrador ($5114-$5117) e página de 8 KB dentro do banco para `address` em
+    /// $8000-$FFFF, conforme o modo de PRG.
+    fn prg_register(&self, address: u16) -> (usize, usize, usize) {
+        let slot = ((address - 0x8000) >> 13) as usize;
+        // (registrador, páginas de 8 KB no banco, página)
+        match (self.prg_mode, slot) {
+            (0, _) => (3, 4, slot),
+            (1, 0 | 1) | (2, 0 | 1) => (1, 2, slot),
+            (1, _) => (3, 2, slot - 2),
+            // Modo 2 em $C000-$FFFF e modo 3: um registrador por página
+            _ => (slot, 1, 0),
+        }
+    }
+
+    /// Onde `address` ($8000-$FFFF) cai: `Ok(offset na PRG-ROM)` ou `Err(offset na PRG-RAM)`.
+    fn prg_offset(&self, address: u16) -> Result<usize, usize> {
+        let (register, pages, page) = self.prg_register(address);
+        let value = self.prg_banks[register];
+        let bank = ((value & 0x7f) as usize & !(pages - 1)) + page;
+        let offset = (address & 0x1fff) as usize;
+        // $5117 sempre mapeia ROM
+        if (value & 0x80) != 0 || register == 3 {
+            Ok((bank * 0x2000 + offset) % self.prg_rom.len())
+        } else {
+            Err((bank & 0x07) * 0x2000 + offset)
+        }
+    }
+
+    fn prg_ram_writable(&self) -> bool {
+        self.prg_ram_protect == [0x02, 0x01]
+    }
+
+    /// Offset na CHR pelo conjunto de bancos dos sprites ($5120-$5127).
+    fn chr_offset_a(&self, address: u16) -> usize {
+        let address = (address & 0x1fff) as usize;
+        let banks = &self.chr_banks_a;
+        match self.chr_mode {
+            0 => (banks[7] as usize) * 0x2000 + address,
+            1 => (banks[(address >> 12) * 4 + 3] as usize) * 0x1000 + (address & 0x0fff),
+            2 => (banks[(address >> 11) * 2 + 1] as usize) * 0x800 + (address & 0x07ff),
+            _ => (banks[address >> 10] as usize) * 0x400 + (address & 0x03ff),
+        }
+    }
+
+    /// Offset na CHR pelo conjunto do background ($5128-$512B), repetido nas duas metades.
+    fn chr_offset_b(&self, address: u16) -> usize {
+        let address = (address & 0x1fff) as usize;
+        let banks = &self.chr_banks_b;
+        match self.chr_mode {
+            0 => (banks[3] as usize) * 0x2000 + address,
+            1 => (banks[3] as usize) * 0x1000 + (address & 0x0fff),
+            2 => (banks[((address >> 11) & 1) * 2 + 1] as usize) * 0x800 + (address & 0x07ff),
+            _ => (banks[(address >> 10) & 0x3] as usize) * 0x400 + (address & 0x03ff),
+        }
+    }
+
+    fn chr_offset(&self, address: u16) -> usize {
+        let offset = match self.fetch {
+            PpuFetch::Background if self.split_tile => {
+                // O fine Y vem do scroll da divisão, não do da PPU
+                let address = ((address & 0x0ff8) | (self.split_y & 0x7)) as usize;
+                (self.split_bank as usize) * 0x1000 + address
+            }
+            PpuFetch::Background if self.exram_mode == 1 => {
+                let bank = ((self.ext_attribute & 0x3f) as usize) | ((self.chr_upper as usize) << 6);
+                bank * 0x1000 + ((address & 0x0fff) as usize)
+            }
+            PpuFetch::Background if self.tall_sprites => self.chr_offset_b(address),
+            PpuFetch::Data if self.tall_sprites && self.last_chr_set_b => {
+                self.chr_offset_b(address)
+            }
+            _ => self.chr_offset_a(address),
+        };
+        offset % self.chr_rom.len()
+    }
+
+    /// Fonte do slot de nametable: 0/1 = VRAM do console, 2 = ExRAM, 3 = fill mode.
+    fn nametable_slot(&self, address: u16) -> u8 {
+        (self.nametable_mapping >> (((address >> 10) & 0x3) * 2)) & 0x3
+    }
+
+    /// Fetch de background substituído pelo cartucho: tile da divisão ou atributo estendido.
+    fn overrides_fetch(&self, address: u16) -> bool {
+        self.fetch == PpuFetch::Background &&
+            (self.split_tile || (self.exram_mode == 1 && (address & 0x3ff) >= 0x3c0))
+    }
+
+    /// Detecta o começo de uma scanline visível e conta até o valor de $5203.
+    fn clock_scanline(&mut self) {
+        if !self.in_frame {
+            self.in_frame = true;
+            self.scanline_counter = 0;
+        } else {
+            self.scanline_counter = self.scanline_counter.wrapping_add(1);
+            if self.scanline_counter == self.irq_compare {
+                self.irq_pending = true;
+            }
+        }
+    }
+}
+
+impl Mapper for Mapper5 {
+    fn read_prg(&self, address: u16) -> u8 {
+        match address {
+            0x5204 => ((self.irq_pending as u8) << 7) | ((self.in_frame as u8) << 6),
+            0x5205 => ((self.multiplicand as u16) * (self.multiplier as u16)) as u8,
+            0x5206 => (((self.multiplicand as u16) * (self.multiplier as u16)) >> 8) as u8,
+            0x5C00..=0x5FFF if self.exram_mode >= 2 => self.exram[(address & 0x3ff) as usize],
+            0x6000..=0x7FFF => {
+                let bank = (self.prg_ram_bank & 0x07) as usize;
+                self.prg_ram.read_offset(bank * 0x2000 + ((address & 0x1fff) as usize))
+            }
+            0x8000..=0xFFFF => match self.prg_offset(address) {
+                Ok(offset) => self.prg_rom[offset],
+                Err(offset) => self.prg_ram.read_offset(offset),
+            },
+            _ => 0,
+        }
+    }
+
+    fn cpu_read_prg(&mut self, address: u16) -> u8 {
+        let value = self.read_prg(address);
+        match address {
+            // A leitura reconhece o IRQ
+            0x5204 => self.irq_pending = false,
+            // A busca do vetor de NMI marca o fim da parte visível do frame
+            0xFFFA | 0xFFFB => self.in_frame = false,
+            _ => {}
+        }
+        value
+    }
+
+    fn write_prg(&mut self, address: u16, value: u8) {
+        match address {
+            0x5100 => self.prg_mode = value & 0x03,
+            0x5101 => self.chr_mode = value & 0x03,
+            0x5102 | 0x5103 => self.prg_ram_protect[(address & 1) as usize] = value & 0x03,
+            0x5104 => self.exram_mode = value & 0x03,
+            0x5105 => self.nametable_mapping = value,
+            0x5106 => self.fill_tile = value,
+            0x5107 => self.fill_attribute = value & 0x03,
+            0x5113 => self.prg_ram_bank = value,
+            0x5114..=0x5117 => self.prg_banks[(address - 0x5114) as usize] = value,
+            0x5120..=0x5127 => {
+                self.chr_banks_a[(address - 0x5120) as usize] =
+                    (value as u16) | ((self.chr_upper as u16) << 8);
+                self.last_chr_set_b = false;
+            }
+            0x5128..=0x512B => {
+                self.chr_banks_b[(address - 0x5128) as usize] =
+                    (value as u16) | ((self.chr_upper as u16) << 8);
+                self.last_chr_set_b = true;
+            }
+            0x5130 => self.chr_upper = value & 0x03,
+            0x5200 => self.split_control = value,
+            0x5201 => self.split_scroll = value,
+            0x5202 => self.split_bank = value,
+            0x5203 => self.irq_compare = value,
+            0x5204 => self.irq_enabled = (value & 0x80) != 0,
+            0x5205 => self.multiplicand = value,
+            0x5206 => self.multiplier = value,
+            0x5C00..=0x5FFF => {
+                let index = (address & 0x3ff) as usize;
+                match self.exram_mode {
+                    // Nos modos de nametable só dá para escrever durante o render
+                    0 | 1 => self.exram[index] = if self.in_frame { value } else { 0 },
+                    2 => self.exram[index] = value,
+                    _ => {}
+                }
+            }
+            0x6000..=0x7FFF if self.prg_ram_writable() => {
+                let bank = (self.prg_ram_bank & 0x07) as usize;
+                self.prg_ram.write_offset(bank * 0x2000 + ((address & 0x1fff) as usize), value);
+            }
+            0x8000..=0xDFFF if self.prg_ram_writable() => {
+                if let Err(offset) = self.prg_offset(address) {
+                    self.prg_ram.write_offset(offset, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn read_chr(&self, address: u16) -> u8 {
+        self.chr_rom[self.chr_offset(address)]
+    }
+
+    fn write_chr(&mut self, address: u16, value: u8) {
+        if self.chr_banks == 0 {
+            let offset = self.chr_offset(address);
+            self.chr_rom[offset] = value;
+        }
+    }
+
+    fn get_mirroring(&self) -> Mirroring {
+        // Só informativo: a PPU usa `nametable`, que cobre todas as combinações de $5105
+        match self.nametable_mapping {
+            0x50 => Mirroring::Horizontal,
+            0x44 => Mirroring::Vertical,
+            0x55 => Mirroring::OneScreenHi,
+            _ => Mirroring::OneScreenLo,
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq_enabled && self.irq_pending
+    }
+
+    fn ppu_access(&mut self, access: PpuAccess) {
+        self.fetch = access.fetch;
+        // Só o fetch do tile (não o do atributo nem os de padrão) abre um novo tile
+        let address = access.address;
+        if access.fetch != PpuFetch::Background || !(0x2000..0x3f00).contains(&address) ||
+            (address & 0x3ff) >= 0x3c0
+        {
+            return;
+        }
+
+        if access.scanline >= 240 {
+            self.in_frame = false;
+        } else if access.cycle == 1 {
+            self.clock_scanline();
+        }
+
+        // Os ciclos 321-336 buscam os dois primeiros tiles da linha seguinte
+        let (tile, line) = if access.cycle >= 321 {
+            ((access.cycle - 321) / 8, if access.scanline >= 261 { 0 } else { access.scanline + 1 })
+        } else {
+            ((access.cycle - 1) / 8 + 2, access.scanline)
+        };
+        let threshold = (self.split_control & 0x1f) as u16;
+        let right_side = (self.split_control & 0x40) != 0;
+        self.split_tile =
+            (self.split_control & 0x80) != 0 &&
+            self.exram_mode <= 1 &&
+            (if right_side { tile >= threshold } else { tile < threshold });
+        if self.split_tile {
+            self.split_y = ((self.split_scroll as u16) + line) % 240;
+            self.split_column = tile % 32;
+        }
+        self.ext_attribute = self.exram[(address & 0x3ff) as usize];
+    }
+
+    fn ppu_register_write(&mut self, address: u16, value: u8) {
+        match address {
+            0x2000 => self.tall_sprites = (value & 0x20) != 0,
+            // Render desligado: a PPU para de buscar e o chip sai do frame
+            0x2001 if (value & 0x18) == 0 => self.in_frame = false,
+            _ => {}
+        }
+    }
+
+    fn nametable(&self, address: u16) -> Nametable {
+        if self.overrides_fetch(address) {
+            return Nametable::Cartridge;
+        }
+        match self.nametable_slot(address) {
+            page @ (0 | 1) => Nametable::Ciram(page as u16),
+            _ => Nametable::Cartridge,
+        }
+    }
+
+    fn read_nametable(&self, address: u16) -> u8 {
+        let offset = (address & 0x3ff) as usize;
+        let attribute = offset >= 0x3c0;
+        if self.fetch == PpuFetch::Background && self.split_tile {
+            let row = self.split_y / 8;
+            let column = self.split_column;
+            if !attribute {
+                return self.exram[(row * 32 + column) as usize];
+            }
+            // O atributo vai com a paleta nos quatro quadrantes, para qualquer shift da PPU
+            let byte = self.exram[(0x3c0 + (row / 4) * 8 + column / 4) as usize];
+            let shift = ((row & 2) << 1) | (column & 2);
+            return ((byte >> shift) & 0x3) * 0x55;
+        }
+        if self.overrides_fetch(address) {
+            return (self.ext_attribute >> 6) * 0x55;
+        }
+        match self.nametable_slot(address) {
+            2 if self.exram_mode <= 1 => self.exram[offset],
+            3 if attribute => self.fill_attribute * 0x55,
+            3 => self.fill_tile,
+            _ => 0,
+        }
+    }
+
+    fn write_nametable(&mut self, address: u16, value: u8) {
+        if self.nametable_slot(address) == 2 && self.exram_mode <= 1 {
+            self.exram[(address & 0x3ff) as usize] = value;
+        }
+    }
+
+    fn prg_ram(&self) -> Option<&PrgRam> {
+        Some(&self.prg_ram)
+    }
+
+    fn prg_ram_mut(&mut self) -> Option<&mut PrgRam> {
+        Some(&mut self.prg_ram)
+    }
+}
@@ -0,0 +1,158 @@
+use std::io::Error;
+use crate::{ apu::APU, bus::BUS, cpu::CPU, input::Key, ppu::PPU, rom::ROM };
+use crate::logging::{ Category, Level };
+
+/// O console completo: CPU com o barramento (PPU, APU, RAM, controles) e o cartucho.
+/// Ponto único de montagem usado pelos frontends.
+pub struct Nes {
+    cpu: CPU,
+}
+
+impl Nes {
+    /// Monta o console em volta do cartucho e liga a energia.
+    pub fn new(rom: ROM) -> Self {
+        // PPU reads pattern tables and nametable mirroring through the cartridge mapper
+        let ppu = PPU::power_up(rom.shared_mapper());
+        log!(Category::Ppu, Level::Info, "Initial mirroring: {:?}", rom.get_mirroring());
+
+        let bus = BUS::new(ppu, rom, APU::new());
+        let mut nes = Nes {
+            cpu: CPU::new(bus),
+        };
+        nes.power_on();
+        nes
+    }
+
+    /// Carrega uma imagem iNES e monta o console.
+    pub fn from_rom_bytes(contents: &[u8]) -> Result<Nes, Error> {
+        Ok(Nes::new(ROM::from_bytes(contents)?))
+    }
+
+    /// Liga (ou desliga e liga) o console: RAM, PPU, APU e registros da CPU voltam ao
+    /// estado de power-up e a CPU segue o vetor de reset.
+    pub fn power_on(&mut self) {
+        self.cpu.bus.power_on();
+        self.cpu.power_on();
+    }
+
+    /// Botão de reset: a RAM e os registros A, X e Y são preservados.
+    pub fn reset(&mut self) {
+        self.cpu.bus.reset();
+        self.cpu.reset();
+    }
+
+    /// Termina a sequência de reset e passa a executar em `address` (ex.: o modo
+    /// automático do nestest em $C000). Os 7 ciclos do reset continuam contados.
+    pub fn start_at(&mut self, address: u16) {
+        self.step_instruction();
+        self.cpu.registers.program_counter = address;
+    }
+
+    /// Executa um ciclo de CPU; retorna `true` quando a PPU completa um frame.
+    pub fn tick(&mut self) -> bool {
+        self.cpu.clock()
+    }
+
+    /// Executa até a PPU completar um frame. Retorna `false` se a CPU parar antes
+    /// (ver `halted`).
+    pub fn run_frame(&mut self) -> bool {
+        while self.cpu.halted().is_none() {
+            if self.cpu.clock() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Termina a instrução em andamento ou executa a próxima inteira (ou o atendimento
+    /// de uma interrupção). Retorna `true` se a PPU completou um frame no meio.
+    pub fn step_instruction(&mut self) -> bool {
+        let mut frame_complete = false;
+        loop {
+            frame_complete |= self.cpu.clock();
+            if self.cpu.remaining_cycles == 0 || self.cpu.halted().is_some() {
+                return frame_complete;
+            }
+        }
+    }
+
+    /// Linha de trace (formato do nestest.log) da próxima instrução; chame entre
+    /// `step_instruction`s.
+    pub fn trace_line(&self) -> String {
+        self.cpu.trace_line()
+    }
+
+    /// (endereço, opcode) em que a CPU parou, se parou.
+    pub fn halted(&self) -> Option<(u16, u8)> {
+        self.cpu.halted()
+    }
+
+    /// Ciclos de CPU desde o power-up.
+    pub fn cycles(&self) -> u64 {
+        self.cpu.cycles
+    }
+
+    /// Último frame completo, 256x240 em RGB (3 bytes por pixel).
+    pub fn framebuffer(&self) -> &[u8] {
+        self.cpu.bus.ppu.get_framebuffer()
+    }
+
+    /// Os 2 KB de RAM interna.
+    pub fn ram(&self) -> &[u8] {
+        self.cpu.bus.ram()
+    }
+
+    /// Lê `address` sem efeitos colaterais (ver `BUS::peek`).
+    pub fn peek(&self, address: u16) -> u8 {
+        self.cpu.bus.peek(address)
+    }
+
+    /// Se o cartucho tem PRG-RAM com bateria (jogos com save).
+    pub fn has_battery(&self) -> bool {
+        self.cpu.bus.rom.battery_backed
+    }
+
+    /// Cópia da PRG-RAM com bateria, para gravar em um `.sav` ou no IndexedDB.
+    pub fn save_data(&self) -> Option<Vec<u8>> {
+        self.cpu.bus.rom.save_data()
+    }
+
+    /// Restaura um save; chame antes de rodar o jogo, como quem liga o console com o
+    /// cartucho já salvo.
+    pub fn load_save_data(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.cpu.bus.rom.load_save_data(data)
+    }
+
+    /// Indica (uma vez) que o save mudou desde a última chamada, para o frontend gravar.
+    pub fn take_save_dirty(&mut self) -> bool {
+        self.cpu.bus.rom.take_save_dirty()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.cpu.bus.apu.set_sample_rate(sample_rate);
+    }
+
+    /// Retorna e esvazia as amostras de áudio geradas desde a última chamada.
+    pub fn take_audio_samples(&mut self) -> Vec<f32> {
+        self.cpu.bus.apu.take_samples()
+    }
+
+    /// Pressiona ou solta `button` no controle do jogador `player` (0 ou 1).
+    pub fn set_button(&mut self, player: usize, button: Key, pressed: bool) {
+        if let Some(controller) = self.cpu.bus.controllers.get_mut(player) {
+            controller.update(button, pressed);
+        }
+    }
+
+    pub fn cpu(&self) -> &CPU {
+        &self.cpu
+    }
+
+    pub fn cpu_mut(&mut self) -> &mut CPU {
+        &mut self.cpu
+    }
+
+    pub fn ppu(&self) -> &PPU {
+        &self.cpu.bus.ppu
+    }
+}
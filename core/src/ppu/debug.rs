@@ -1,15 +1,13 @@
 use super::*;
 use super::colors::convert_color;
+use crate::logging::{ Category, Level };
 
 impl PPU {
     pub fn debug_pattern_tables(&self) {
         for addr in 0..0x2000 {
-            if self.vram[addr] != 0 {
-                web_sys::console::log_1(&format!(
-                    "Pattern table data at {:04X}: {:02X}",
-                    addr,
-                    self.vram[addr]
-                ).into());
+            let data = self.read_ppu_memory(addr);
+            if data != 0 {
+                log!(Category::Ppu, Level::Debug, "Pattern table data at {:04X}: {:02X}", addr, data);
             }
         }
     }
@@ -21,16 +19,18 @@ impl PPU {
             for offset in 0..0x400 {
                 if self.vram[base + offset] != 0 {
                     non_zero = true;
-                    web_sys::console::log_1(&format!(
+                    log!(
+                        Category::Ppu,
+                        Level::Debug,
                         "NT{} data at {:04X}: {:02X}",
                         nt,
                         base + offset,
                         self.vram[base + offset]
-                    ).into());
+                    );
                 }
             }
             if !non_zero {
-                web_sys::console::log_1(&format!("Nametable {} is empty", nt).into());
+                log!(Category::Ppu, Level::Debug, "Nametable {} is empty", nt);
             }
         }
     }
@@ -0,0 +1,82 @@
+use super::*;
+use crate::mapper::{ Nametable, PpuAccess, PpuFetch };
+
+impl PPU {
+    /// Fetch do render: com o render ligado o acesso também é avisado ao cartucho
+    /// (ver `Mapper::ppu_access`).
+    pub(crate) fn fetch_ppu_memory(&mut self, addr: u16, fetch: PpuFetch) -> u8 {
+        if self.rendering_enabled {
+            self.notify_mapper(addr, fetch);
+        }
+        self.read_ppu_memory(addr)
+    }
+
+    pub(crate) fn notify_mapper(&mut self, addr: u16, fetch: PpuFetch) {
+        let access = PpuAccess {
+            address: addr & 0x3fff,
+            fetch,
+            scanline: self.scanline,
+            cycle: self.cycle,
+            dot: (self.frame * 262 + (self.scanline as u64)) * 341 + (self.cycle as u64),
+        };
+        self.mapper.borrow_mut().ppu_access(access);
+    }
+
+    pub(crate) fn read_ppu_memory(&self, addr: u16) -> u8 {
+        let addr = addr & 0x3fff;
+        match addr {
+            0x0000..=0x1fff => self.mapper.borrow().read_chr(addr),
+            0x2000..=0x3eff => {
+                let mapper = self.mapper.borrow();
+                match mapper.nametable(addr & 0x2fff) {
+                    Nametable::Ciram(page) => self.vram[Self::ciram_address(page, addr)],
+                    Nametable::Cartridge => mapper.read_nametable(addr & 0x2fff),
+                }
+            },
+            0x3f00..=0x3fff => {
+                let addr = addr & 0x1f;
+                let addr = match addr {
+                    0x10 => 0x00,
+                    0x14 => 0x04,
+                    0x18 => 0x08,
+                    0x1c => 0x0c,
+                    _ => addr
+                };
+                self.palette[addr as usize]
+            },
+            _ => 0
+        }
+    }
+
+    pub(crate) fn write_ppu_memory(&mut self, addr: u16, data: u8) {
+        let addr = addr & 0x3fff;
+        match addr {
+            0x0000..=0x1fff => self.mapper.borrow_mut().write_chr(addr, data),
+            0x2000..=0x3eff => {
+                let mut mapper = self.mapper.borrow_mut();
+                match mapper.nametable(addr & 0x2fff) {
+                    Nametable::Ciram(page) => self.vram[Self::ciram_address(page, addr)] = data,
+                    Nametable::Cartridge => mapper.write_nametable(addr & 0x2fff, data),
+                }
+            },
+            0x3f00..=0x3fff => {
+                let addr = addr & 0x1f;
+                let addr = match addr {
+                    0x10 => 0x00,
+                    0x14 => 0x04,
+                    0x18 => 0x08,
+                    0x1c => 0x0c,
+                    _ => addr
+                };
+                self.palette[addr as usize] = data;
+            },
+            _ => {}
+        }
+    }
+
+    // O mapeamento das nametables é consultado no cartucho a cada acesso: MMC1, MMC3,
+    // AxROM e MMC5 o trocam em tempo de execução
+    fn ciram_address(page: u16, addr: u16) -> usize {
+        (0x2000 + page * 0x400 + (addr & 0x3ff)) as usize
+    }
+}
@@ -8,9 +8,9 @@ mod memory;
 mod registers;
 mod debug;
 mod colors;
+mod power_up;
 
-use crate::rom::Mirroring;
-use self::colors::convert_color;
+use crate::mapper::SharedMapper;
 
 pub struct PPU {
     // MMIO registers (memory mapped I/O)
@@ -33,6 +33,7 @@ pub struct PPU {
     read_buffer: u8,
 
     // Memory
+    mapper: SharedMapper,     // Cartucho: pattern tables (CHR-ROM/RAM) e espelhamento
     vram: [u8; 0x4000],       // VRAM (16KB)
     palette: [u8; 32],        // Palette RAM (32 bytes)
     oam: [u8; 256],          // Primary OAM (256 bytes)
@@ -63,7 +64,7 @@ pub struct PPU {
     sprite_zero_being_rendered: bool,
 
     // Output
-    framebuffer: [u8; 256 * 240 * 3],
+    framebuffer: Vec<u8>,     // 256x240 RGB; no heap para não estourar a pilha ao mover a PPU
     
     // NMI flags
     pub(crate) nmi_occurred: bool,
@@ -72,18 +73,16 @@ pub struct PPU {
     nmi_delay: u8,
 
     // Additional state
-    mirroring: Mirroring,
     pub(crate) odd_frame: bool,  
     pub(crate) rendering_enabled: bool,
 }
 
 pub struct StepResult {
     pub new_frame: bool,
-    pub vblank_nmi: bool,
 }
 
 impl PPU {
-    pub fn new() -> Self {
+    pub fn new(mapper: SharedMapper) -> Self {
         PPU {
             // Initialize registers to power-up state
             ctrl: 0,
@@ -103,6 +102,7 @@ impl PPU {
             read_buffer: 0,
 
             // Memory
+            mapper,
             vram: [0; 0x4000],
             palette: [0; 32],
             oam: [0xFF; 256],
@@ -127,12 +127,11 @@ impl PPU {
             sprite_indexes: [0; 8],
             sprite_zero_hit_possible: false,
             sprite_zero_being_rendered: false,
-            framebuffer: [0; 256 * 240 * 3],
+            framebuffer: vec![0; 256 * 240 * 3],
             nmi_occurred: false,
             nmi_output: false,
             nmi_previous: false,
             nmi_delay: 0,
-            mirroring: Mirroring::Horizontal,
             odd_frame: false,
             rendering_enabled: false,
         }
@@ -148,19 +147,7 @@ impl PPU {
          self.scroll, self.addr as u8, self.addr)
     }
 
-    pub fn set_mirroring(&mut self, mirroring: Mirroring) {
-        self.mirroring = mirroring;
-    }
-
-    pub fn load_chr_data(&mut self, data: &[u8]) {
-        let len = data.len().min(self.vram.len());
-        self.vram[..len].copy_from_slice(&data[..len]);
-    }
-
     pub(crate) fn is_rendering_enabled(&self) -> bool {
         (self.mask & 0x18) != 0
     }
 }
-
-// Export main struct
-pub use self::rendering::*;
\ No newline at end of file
@@ -17,4 +17,10 @@ impl PPU {
             self.nmi_occurred = false;
         }
     }
+
+    /// Nível da linha de NMI: ativa enquanto a flag de VBlank e o bit 7 do PPUCTRL estão setados.
+    /// A CPU detecta a borda de subida (ver `BUS::step_ppu`).
+    pub(crate) fn nmi_line(&self) -> bool {
+        self.nmi_output && self.nmi_occurred
+    }
 }
@@ -0,0 +1,21 @@
+use super::PPU;
+use crate::mapper::SharedMapper;
+
+impl PPU {
+    pub fn power_up(mapper: SharedMapper) -> Self {
+        PPU::new(mapper)
+    }
+
+    /// Sinal de reset: PPUCTRL, PPUMASK, PPUSCROLL, o latch de escrita e o buffer de
+    /// leitura voltam a zero. PPUSTATUS, OAMADDR, PPUADDR, VRAM, OAM e paleta não mudam.
+    pub fn reset(&mut self) {
+        self.write_control(0);
+        self.write_mask(0);
+        self.scroll = 0;
+        self.t = 0;
+        self.x = 0;
+        self.w = false;
+        self.read_buffer = 0;
+        self.odd_frame = false;
+    }
+}
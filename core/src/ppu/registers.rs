@@ -1,4 +1,5 @@
 use super::*;
+use crate::mapper::PpuFetch;
 
 impl PPU {
     pub fn read_register(&mut self, address: u16) -> u8 {
@@ -7,7 +8,9 @@ impl PPU {
             0x2001 => 0,
             0x2002 => {
                 let result = self.status;
+                // Ler o status derruba a linha de NMI até o próximo VBlank
                 self.status &= 0x7f;
+                self.nmi_occurred = false;
                 self.w = false;
                 result
             },
@@ -39,6 +42,7 @@ impl PPU {
 
     fn read_data(&mut self) -> u8 {
         let addr = self.v & 0x3FFF;
+        self.notify_mapper(addr, PpuFetch::Data);
         let data = self.read_buffer;
         self.read_buffer = self.read_ppu_memory(addr);
         
@@ -53,7 +57,43 @@ impl PPU {
 
     fn write_data(&mut self, value: u8) {
         let addr = self.v & 0x3FFF;
+        self.notify_mapper(addr, PpuFetch::Data);
         self.write_ppu_memory(addr, value);
         self.v += if (self.ctrl & 0x04) != 0 { 32 } else { 1 };
     }
+
+    pub(crate) fn write_control(&mut self, value: u8) {
+        self.ctrl = value;
+        self.t = (self.t & 0xf3ff) | (((value as u16) & 0x03) << 10);
+        // Habilitar o NMI durante o VBlank gera uma borda na linha (ver BUS::step_ppu)
+        self.nmi_output = (value & 0x80) != 0;
+    }
+
+    pub(crate) fn write_mask(&mut self, value: u8) {
+        self.mask = value;
+        self.rendering_enabled = self.is_rendering_enabled();
+    }
+
+    pub(crate) fn write_scroll(&mut self, value: u8) {
+        if !self.w {
+            self.t = (self.t & 0xffe0) | ((value as u16) >> 3);
+            self.x = value & 0x07;
+            self.w = true;
+        } else {
+            self.t = (self.t & 0x8fff) | (((value as u16) & 0x07) << 12);
+            self.t = (self.t & 0xfc1f) | (((value as u16) & 0xf8) << 2);
+            self.w = false;
+        }
+    }
+
+    pub(crate) fn write_address(&mut self, value: u8) {
+        if !self.w {
+            self.t = (self.t & 0x00ff) | (((value as u16) & 0x3f) << 8);
+            self.w = true;
+        } else {
+            self.t = (self.t & 0xff00) | (value as u16);
+            self.v = self.t;
+            self.w = false;
+        }
+    }
 }
@@ -0,0 +1,242 @@
+use super::*;
+use super::colors::convert_color;
+use crate::mapper::PpuFetch;
+
+impl PPU {
+    pub fn step(&mut self) -> StepResult {
+        let mut result = StepResult {
+            new_frame: false,
+        };
+
+        // Atualiza estado de rendering
+        self.rendering_enabled = self.is_rendering_enabled();
+
+        // Manejo especial do último ciclo do frame para frames ímpares
+        if self.rendering_enabled && self.odd_frame && self.scanline == 261 && self.cycle == 339 {
+            self.cycle = 0;
+            self.scanline = 0;
+            self.odd_frame = !self.odd_frame;
+            self.frame += 1;
+            result.new_frame = true;
+            return result;
+        }
+
+        // Add actual pixel rendering during visible scanlines
+        if self.scanline < 240 && self.cycle > 0 && self.cycle <= 256 {
+            self.render_pixel();
+        }
+
+        // Pre-render scanline (261)
+        if self.scanline == 261 {
+            if self.cycle == 1 {
+                self.status &= !0xE0; // Clear VBlank, sprite 0 hit, sprite overflow
+                self.nmi_occurred = false;
+            }
+
+            // Transferência vertical durante pré-render
+            if self.cycle >= 280 && self.cycle <= 304 && self.rendering_enabled {
+                self.transfer_address_y();
+            }
+        }
+
+        // Scanlines visíveis (0-239) e pré-render
+        if self.scanline <= 239 || self.scanline == 261 {
+            // Ciclos para fetch de tiles e renderização
+            match self.cycle {
+                0 => {} // Idle cycle
+                1..=256 => {
+                    self.update_shifters();
+                    
+                    match (self.cycle - 1) % 8 {
+                        0 => self.fetch_nametable_byte(),
+                        2 => self.fetch_attribute_byte(),
+                        4 => self.fetch_pattern_low(),
+                        6 => self.fetch_pattern_high(),
+                        7 => self.increment_scroll_x(),
+                        _ => {}
+                    }
+
+                    if self.cycle == 256 {
+                        self.increment_scroll_y();
+                    }
+                }
+                257 => {
+                    self.transfer_address_x();
+                    if self.scanline < 240 {
+                        self.evaluate_sprites();
+                    } else {
+                        // A pré-render também busca os 8 sprites (todos vazios)
+                        self.fetch_empty_sprite_slots(0);
+                    }
+                }
+                321..=336 => {
+                    self.update_shifters();
+                    
+                    match (self.cycle - 321) % 8 {
+                        0 => self.fetch_nametable_byte(),
+                        2 => self.fetch_attribute_byte(),
+                        4 => self.fetch_pattern_low(),
+                        6 => self.fetch_pattern_high(),
+                        7 => self.increment_scroll_x(),
+                        _ => {}
+                    }
+                }
+                337..=340 => { /* Dois NT bytes dummy */ }
+                _ => {}
+            }
+        }
+
+        // VBlank (scanlines 241-260)
+        if self.scanline == 241 && self.cycle == 1 {
+            self.status |= 0x80; // Set VBlank flag
+            self.nmi_occurred = true;
+        }
+
+        // Avança ciclo/scanline
+        self.cycle += 1;
+        if self.cycle > 340 {
+            self.cycle = 0;
+            self.scanline += 1;
+            if self.scanline > 261 {
+                self.scanline = 0;
+                self.odd_frame = !self.odd_frame;
+                self.frame += 1;
+                result.new_frame = true;
+            }
+        }
+
+        result
+    }
+
+    // Método render_pixel completo
+    fn render_pixel(&mut self) {
+        if self.cycle == 0 || self.cycle > 256 || self.scanline >= 240 {
+            return;
+        }
+
+        let x = (self.cycle - 1) as usize;
+        let y = self.scanline as usize;
+
+        let mut bg_pixel = 0u8;
+        let mut bg_palette = 0u8;
+
+        if (self.mask & 0x08) != 0 {
+            let bit_mux = 0x8000 >> self.x;
+            
+            let p0 = if (self.bg_shifter_pattern_lo & bit_mux) != 0 { 1 } else { 0 };
+            let p1 = if (self.bg_shifter_pattern_hi & bit_mux) != 0 { 2 } else { 0 };
+            bg_pixel = p0 | p1;
+
+            let pal0 = if (self.bg_shifter_attrib_lo & bit_mux) != 0 { 1 } else { 0 };
+            let pal1 = if (self.bg_shifter_attrib_hi & bit_mux) != 0 { 2 } else { 0 };
+            bg_palette = pal0 | pal1;
+        }
+
+        let mut sprite_pixel = 0u8;
+        let mut sprite_palette = 0u8;
+        let mut sprite_priority = false;
+
+        if (self.mask & 0x10) != 0 {
+            for i in 0..self.sprite_count {
+                let offset = (self.cycle - 1) as i16 - self.sprite_positions[i] as i16;
+                
+                if offset >= 0 && offset < 8 {
+                    let shift = 7 - offset;
+                    let pattern = self.sprite_patterns[i];
+                    
+                    let pixel = ((pattern >> shift) & 0x01) | 
+                               (((pattern >> 8) >> shift) & 0x01) << 1;
+
+                    if pixel != 0 {
+                        sprite_pixel = pixel as u8;
+                        sprite_palette = 4 + ((self.secondary_oam[i * 4 + 2] & 0x03) as u8);
+                        sprite_priority = (self.secondary_oam[i * 4 + 2] & 0x20) == 0;
+                        break;
+                    }
+                }
+            }
+        }
+
+        let pixel_color = if sprite_pixel == 0 {
+            let palette_addr = if bg_pixel == 0 { 0 } else { (bg_palette << 2) | bg_pixel };
+            self.read_ppu_memory(0x3F00 | palette_addr as u16)
+        } else if bg_pixel == 0 {
+            self.read_ppu_memory(0x3F00 | (sprite_palette << 2 | sprite_pixel) as u16)
+        } else {
+            if sprite_priority {
+                self.read_ppu_memory(0x3F00 | (sprite_palette << 2 | sprite_pixel) as u16)
+            } else {
+                self.read_ppu_memory(0x3F00 | (bg_palette << 2 | bg_pixel) as u16)
+            }
+        };
+
+        let (r, g, b) = convert_color(pixel_color);
+        let offset = (y * 256 + x) * 3;
+        if offset + 2 < self.framebuffer.len() {
+            self.framebuffer[offset] = r;
+            self.framebuffer[offset + 1] = g;
+            self.framebuffer[offset + 2] = b;
+        }
+    }
+
+    fn update_shifters(&mut self) {
+        if (self.mask & 0x08) != 0 {
+            self.bg_shifter_pattern_lo <<= 1;
+            self.bg_shifter_pattern_hi <<= 1;
+            self.bg_shifter_attrib_lo <<= 1;
+            self.bg_shifter_attrib_hi <<= 1;
+
+            // Carregar novos bits se estivermos em um ciclo de tile fetch; os dois tiles
+            // buscados em 321-336 são os primeiros da próxima linha
+            if self.cycle.is_multiple_of(8) && ((1..=256).contains(&self.cycle) || self.cycle >= 328) {
+                self.load_background_shifters();
+            }
+        }
+
+        if (self.mask & 0x10) != 0 {
+            for i in 0..self.sprite_count {
+                self.sprite_patterns[i] <<= 1;
+            }
+        }
+    }
+
+    fn load_background_shifters(&mut self) {
+        self.bg_shifter_pattern_lo =
+            (self.bg_shifter_pattern_lo & 0xff00) | (self.bg_next_tile_lsb as u16);
+        self.bg_shifter_pattern_hi =
+            (self.bg_shifter_pattern_hi & 0xff00) | (self.bg_next_tile_msb as u16);
+
+        self.bg_shifter_attrib_lo =
+            (self.bg_shifter_attrib_lo & 0xff00) |
+            (if (self.bg_next_tile_attrib & 0x01) != 0 { 0xff } else { 0x00 });
+        self.bg_shifter_attrib_hi =
+            (self.bg_shifter_attrib_hi & 0xff00) |
+            (if (self.bg_next_tile_attrib & 0x02) != 0 { 0xff } else { 0x00 });
+    }
+
+    fn fetch_nametable_byte(&mut self) {
+        let addr = 0x2000 | (self.v & 0x0FFF);
+        self.bg_next_tile_id = self.fetch_ppu_memory(addr, PpuFetch::Background);
+    }
+
+    fn fetch_attribute_byte(&mut self) {
+        let addr = 0x23C0 | (self.v & 0x0C00) | ((self.v >> 4) & 0x38) | ((self.v >> 2) & 0x07);
+        let shift = ((self.v >> 4) & 4) | (self.v & 2);
+        let attribute = self.fetch_ppu_memory(addr, PpuFetch::Background);
+        self.bg_next_tile_attrib = ((attribute >> shift) & 3) * 0x55;
+    }
+
+    fn fetch_pattern_low(&mut self) {
+        let fine_y = (self.v >> 12) & 7;
+        let table = ((self.ctrl & 0x10) >> 4) as u16;
+        let addr = (table << 12) | ((self.bg_next_tile_id as u16) << 4) | fine_y;
+        self.bg_next_tile_lsb = self.fetch_ppu_memory(addr, PpuFetch::Background);
+    }
+
+    fn fetch_pattern_high(&mut self) {
+        let fine_y = (self.v >> 12) & 7;
+        let table = ((self.ctrl & 0x10) >> 4) as u16;
+        let addr = (table << 12) | ((self.bg_next_tile_id as u16) << 4) | fine_y | 8;
+        self.bg_next_tile_msb = self.fetch_ppu_memory(addr, PpuFetch::Background);
+    }
+}
\ No newline at end of file
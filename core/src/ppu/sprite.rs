@@ -1,4 +1,5 @@
 use super::*;
+use crate::mapper::PpuFetch;
 
 impl PPU {
     pub(crate) fn evaluate_sprites(&mut self) {
@@ -73,8 +74,8 @@ impl PPU {
 
             // Aqui precisaríamos calcular o padrão exato com base no Y do sprite
             // Simplificado para este exemplo
-            let sprite_pattern_lo = self.read_ppu_memory(pattern_addr_lo);
-            let sprite_pattern_hi = self.read_ppu_memory(pattern_addr_hi);
+            let sprite_pattern_lo = self.fetch_ppu_memory(pattern_addr_lo, PpuFetch::Sprite);
+            let sprite_pattern_hi = self.fetch_ppu_memory(pattern_addr_hi, PpuFetch::Sprite);
 
             // Armazenar dados para uso durante o scanline
             self.sprite_patterns[i] =
@@ -83,6 +84,22 @@ impl PPU {
             self.sprite_priorities[i] = (sprite_attr & 0x20) >> 5;
             self.sprite_indexes[i] = i as u8;
         }
+
+        self.fetch_empty_sprite_slots(self.sprite_count);
+    }
+
+    /// Os slots sem sprite ainda buscam o padrão do tile $FF; o MMC3 conta scanlines
+    /// pela linha A12 desses fetches.
+    pub(crate) fn fetch_empty_sprite_slots(&mut self, first: usize) {
+        let pattern_addr = if (self.ctrl & 0x20) == 0 {
+            (((self.ctrl & 0x08) as u16) << 9) | 0x0ff0
+        } else {
+            0x1fe0
+        };
+        for _ in first..8 {
+            self.fetch_ppu_memory(pattern_addr, PpuFetch::Sprite);
+            self.fetch_ppu_memory(pattern_addr + 8, PpuFetch::Sprite);
+        }
     }
 
     pub(crate) fn get_sprite_pixel(&self, x: u8) -> (u8, u8, bool) {
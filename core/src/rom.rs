@@ -0,0 +1,326 @@
+use std::cell::RefCell;
+use std::io::{Error, ErrorKind};
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use crate::logging::{ Category, Level };
+use crate::mapper::{Mapper0, Mapper1, Mapper2, Mapper3, Mapper4, Mapper5, Mapper7, SharedMapper, PRG_RAM_SIZE};
+
+/// Jogos com PRG-RAM com bateria cujos dumps costumam vir sem o bit 1 do byte 6 do
+/// cabeçalho: (CRC32 da PRG-ROM, título).
+const KNOWN_BATTERY_BOARDS: &[(u32, &str)] = &[
+    (0x3fe272fb, "The Legend of Zelda"),
+];
+
+/// CRC32 (polinômio IEEE, o mesmo dos bancos de dados de dumps e do PNG).
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if (crc & 1) != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+pub struct ROM {
+    pub header: Vec<u8>,
+    pub mapper: SharedMapper,
+    pub mirroring: Mirroring,
+    pub battery_backed: bool,
+    pub mapper_number: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mirroring {
+    Horizontal,
+    Vertical,
+    OneScreenLo,
+    OneScreenHi,
+    FourScreen,
+}
+
+impl Mirroring {
+    /// Página da VRAM interna usada pela nametable `index` (0-3, de $2000 a $2C00).
+    pub fn nametable_page(self, index: u16) -> u16 {
+        match self {
+            Mirroring::Horizontal => index / 2,
+            Mirroring::Vertical => index % 2,
+            Mirroring::FourScreen => index,
+            Mirroring::OneScreenLo => 0,
+            Mirroring::OneScreenHi => 1,
+        }
+    }
+}
+
+impl ROM {
+    pub fn new<P: AsRef<Path>>(rom_path: P) -> Result<ROM, Error> {
+        let contents: Vec<u8> = fs::read(rom_path)?;
+        Self::from_bytes(&contents)
+    }
+
+    pub fn from_bytes(contents: &[u8]) -> Result<ROM, Error> {
+        // Verify minimum size and iNES header
+        if contents.len() < 16 || &contents[0..4] != b"NES\x1A" {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Invalid iNES header"
+            ));
+        }
+
+        let _header = &contents[0..16];
+        // Log ROM size and header for debugging
+        log!(
+            Category::Rom,
+            Level::Info,
+            "Loading ROM - Size: {}, Header: {:02x?}",
+            contents.len(),
+            &contents[0..16]
+        );
+
+        // Todos os mappers indexam a PRG-ROM pelo número de bancos
+        if contents[4] == 0 {
+            return Err(Error::new(ErrorKind::InvalidData, "iNES header declares no PRG-ROM"));
+        }
+
+        let header = contents[0..16].to_vec();
+        let prg_rom_size = contents[4] as usize * 16384;
+        let chr_rom_size = contents[5] as usize * 8192;
+
+        // Get mapper number and flags
+        let mapper_number = (contents[6] >> 4) | (contents[7] & 0xF0);
+        let vertical_mirroring = (contents[6] & 0x01) != 0;
+        let battery_backed = (contents[6] & 0x02) != 0;
+        let has_trainer = (contents[6] & 0x04) != 0;
+        let four_screen = (contents[6] & 0x08) != 0;
+
+        // PRG-RAM: no NES 2.0 o byte 10 traz os tamanhos volátil e com bateria (64 << n
+        // bytes, 0 = nenhuma); no iNES 1.0 o byte 8 conta blocos de 8 KB, com 0 valendo 8 KB
+        let nes2 = (contents[7] & 0x0C) == 0x08;
+        let prg_ram_size = if nes2 {
+            let shift_size = |shift: u8| if shift == 0 { 0 } else { 64usize << shift };
+            shift_size(contents[10] & 0x0F) + shift_size(contents[10] >> 4)
+        } else {
+            (contents[8].max(1) as usize) * PRG_RAM_SIZE
+        };
+        // Variante da placa (NES 2.0); 0 = não especificada
+        let submapper = if nes2 { contents[8] >> 4 } else { 0 };
+
+        // Log detailed ROM info
+        log!(
+            Category::Rom,
+            Level::Info,
+            "ROM Info - PRG: {}KB, CHR: {}KB, PRG-RAM: {}KB, Mapper: {}, Flags: v={} b={} t={} f={}",
+            prg_rom_size / 1024,
+            chr_rom_size / 1024,
+            prg_ram_size / 1024,
+            mapper_number,
+            vertical_mirroring,
+            battery_backed,
+            has_trainer,
+            four_screen
+        );
+
+        // Calculate offsets and verify size
+        let header_size = 16 + if has_trainer { 512 } else { 0 };
+        log!(Category::Rom, Level::Debug, "Header Size: {}", header_size);
+        let expected_size = header_size + prg_rom_size + chr_rom_size;
+        if contents.len() < expected_size {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                format!(
+                    "ROM too small. Expected {}, got {}",
+                    expected_size,
+                    contents.len()
+                )
+            ));
+        }
+
+        // Extract PRG-ROM (with bounds checking)
+        let prg_rom_start = header_size;
+        let prg_rom_end = prg_rom_start + prg_rom_size;
+        log!(Category::Rom, Level::Debug, "PRG ROM Start: {}, End: {}", prg_rom_start, prg_rom_end);
+        if prg_rom_end > contents.len() {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                format!("PRG ROM ends at {}, but ROM size is {}", prg_rom_end, contents.len())
+            ));
+        }
+        let prg_rom = contents[prg_rom_start..prg_rom_end].to_vec();
+        log!(Category::Rom, Level::Debug, "PRG ROM Size: {}", prg_rom.len());
+
+        // Dumps com cabeçalho antigo às vezes perdem o bit de bateria; placas conhecidas
+        // são reconhecidas pelo CRC32 da PRG-ROM
+        let prg_crc = crc32(&prg_rom);
+        let battery_backed = battery_backed || {
+            let known = KNOWN_BATTERY_BOARDS.iter().find(|(crc, _)| *crc == prg_crc);
+            if let Some((_, title)) = known {
+                log!(Category::Rom, Level::Info, "Header has no battery flag; {} has one", title);
+            }
+            known.is_some()
+        };
+
+        // Create CHR-ROM/RAM
+        let chr_rom = if chr_rom_size == 0 {
+            log!(Category::Rom, Level::Debug, "CHR ROM Size is 0, creating CHR RAM");
+            vec![0; 8192] // 8KB of CHR-RAM
+        } else {
+            let chr_rom_start = prg_rom_end;
+            let chr_rom_end = chr_rom_start + chr_rom_size;
+            log!(Category::Rom, Level::Debug, "CHR ROM Start: {}, End: {}", chr_rom_start, chr_rom_end);
+            if chr_rom_end > contents.len() {
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    format!("CHR ROM ends at {}, but ROM size is {}", chr_rom_end, contents.len())
+                ));
+            }
+            let chr_rom = contents[chr_rom_start..chr_rom_end].to_vec();
+            log!(Category::Rom, Level::Debug, "CHR ROM Size: {}", chr_rom.len());
+            chr_rom
+        };
+
+        // Determine mirroring type
+        let mirroring = if four_screen {
+            Mirroring::FourScreen
+        } else if vertical_mirroring {
+            Mirroring::Vertical
+        } else {
+            Mirroring::Horizontal
+        };
+
+        Ok(ROM {
+            header: contents[0..16].to_vec(),
+            mapper: match mapper_number {
+                0 => Rc::new(RefCell::new(Mapper0::new(
+                    prg_rom,
+                    chr_rom,
+                    contents[4],
+                    contents[5],
+                    mirroring,
+                    prg_ram_size
+                ))),
+                1 => Rc::new(RefCell::new(Mapper1::new(
+                    prg_rom,
+                    chr_rom,
+                    contents[4],
+                    contents[5],
+                    prg_ram_size
+                ))),
+                2 => Rc::new(RefCell::new(Mapper2::new(
+                    prg_rom,
+                    chr_rom,
+                    contents[4],
+                    contents[5],
+                    mirroring
+                ))),
+                // Submapper 1: placa sem bus conflicts; os demais CNROM têm
+                3 => Rc::new(RefCell::new(Mapper3::new(
+                    prg_rom,
+                    chr_rom,
+                    contents[5],
+                    mirroring,
+                    submapper != 1
+                ))),
+                4 => Rc::new(RefCell::new(Mapper4::new(
+                    prg_rom,
+                    chr_rom,
+                    contents[5],
+                    mirroring,
+                    prg_ram_size
+                ))),
+                5 => Rc::new(RefCell::new(Mapper5::new(
+                    prg_rom,
+                    chr_rom,
+                    contents[5],
+                    prg_ram_size
+                ))),
+                // Submapper 2: AMROM, a única variante com bus conflicts
+                7 => Rc::new(RefCell::new(Mapper7::new(
+                    prg_rom,
+                    chr_rom,
+                    contents[5],
+                    submapper == 2
+                ))),
+                _ => return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    format!("Unsupported mapper: {}", mapper_number)
+                )),
+            },
+            mirroring,
+            battery_backed,
+            mapper_number,
+        })
+    }
+
+    fn parse_header(_header: &[u8]) -> Result<(), &'static str> {
+        // Implementation here
+        Ok(())
+    }
+
+    pub fn read(&mut self, address: u16) -> u8 {
+        self.mapper.borrow_mut().cpu_read_prg(address)
+    }
+
+    /// Leitura sem efeitos colaterais, para `BUS::peek`.
+    pub fn peek(&self, address: u16) -> u8 {
+        self.mapper.borrow().read_prg(address)
+    }
+
+    pub fn write(&mut self, address: u16, value: u8) {
+        self.mapper.borrow_mut().write_prg(address, value);
+    }
+
+    pub fn read_chr(&self, address: u16) -> u8 {
+        self.mapper.borrow().read_chr(address)
+    }
+
+    pub fn write_chr(&mut self, address: u16, value: u8) {
+        self.mapper.borrow_mut().write_chr(address, value);
+    }
+
+    pub fn get_mirroring(&self) -> Mirroring {
+        self.mapper.borrow().get_mirroring()
+    }
+
+    pub fn irq_pending(&self) -> bool {
+        self.mapper.borrow().irq_pending()
+    }
+
+    pub fn ppu_register_write(&mut self, address: u16, value: u8) {
+        self.mapper.borrow_mut().ppu_register_write(address, value);
+    }
+
+    /// Conteúdo da PRG-RAM com bateria, para gravar o save (`None` se o cartucho não tem).
+    pub fn save_data(&self) -> Option<Vec<u8>> {
+        if !self.battery_backed {
+            return None;
+        }
+        self.mapper.borrow().prg_ram().map(|ram| ram.data().to_vec())
+    }
+
+    /// Restaura um save gravado a partir de `save_data`.
+    pub fn load_save_data(&mut self, data: &[u8]) -> Result<(), Error> {
+        if !self.battery_backed {
+            return Err(Error::new(ErrorKind::Unsupported, "Cartridge has no battery-backed RAM"));
+        }
+        match self.mapper.borrow_mut().prg_ram_mut() {
+            Some(ram) => ram.load(data),
+            None => Err(Error::new(ErrorKind::Unsupported, "Cartridge has no PRG-RAM")),
+        }
+    }
+
+    /// Indica (uma vez) que o save mudou desde a última chamada.
+    pub fn take_save_dirty(&mut self) -> bool {
+        self.battery_backed &&
+            self.mapper
+                .borrow_mut()
+                .prg_ram_mut()
+                .is_some_and(|ram| ram.take_dirty())
+    }
+
+    /// Retorna um novo handle para o mapper, usado pela PPU nas leituras de CHR.
+    pub fn shared_mapper(&self) -> SharedMapper {
+        Rc::clone(&self.mapper)
+    }
+}
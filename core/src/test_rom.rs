@@ -0,0 +1,102 @@
+//! Protocolo de resultado das ROMs de teste do blargg (cpu, ppu_vbl_nmi, apu_test,
+//! mmc3_test...): status em $6000, assinatura DE B0 61 em $6001-$6003 e uma mensagem
+//! ASCII terminada em zero a partir de $6004.
+//!
+//! Status: $80 = rodando, $81 = pede um reset (depois de pelo menos 100 ms),
+//! $00-$7F = terminado, com 0 = passou.
+
+use crate::Nes;
+
+const STATUS_ADDRESS: u16 = 0x6000;
+const SIGNATURE_ADDRESS: u16 = 0x6001;
+const SIGNATURE: [u8; 3] = [0xDE, 0xB0, 0x61];
+const TEXT_ADDRESS: u16 = 0x6004;
+const TEXT_END: u16 = 0x7FFF;
+
+const STATUS_RUNNING: u8 = 0x80;
+const STATUS_NEEDS_RESET: u8 = 0x81;
+/// ~100 ms de espera antes de apertar o reset.
+const RESET_DELAY_FRAMES: u32 = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    /// Código de erro informado pela ROM.
+    Failed(u8),
+    /// O limite de frames acabou antes da ROM terminar (ou sem assinatura em $6001).
+    TimedOut,
+    /// (endereço, opcode) em que a CPU parou.
+    Halted(u16, u8),
+}
+
+#[derive(Clone, Debug)]
+pub struct TestReport {
+    pub status: TestStatus,
+    /// Texto em $6004, se a ROM escreveu a assinatura.
+    pub message: String,
+    pub frames: u32,
+}
+
+impl TestReport {
+    pub fn passed(&self) -> bool {
+        self.status == TestStatus::Passed
+    }
+}
+
+/// Roda a ROM até ela informar o resultado em $6000, atendendo os pedidos de reset,
+/// ou até `max_frames`.
+pub fn run(nes: &mut Nes, max_frames: u32) -> TestReport {
+    let mut frames = 0;
+    let mut reset_at = None;
+
+    let status = loop {
+        if let Some((address, opcode)) = nes.halted() {
+            break TestStatus::Halted(address, opcode);
+        }
+        if has_signature(nes) {
+            match nes.peek(STATUS_ADDRESS) {
+                STATUS_RUNNING => {}
+                STATUS_NEEDS_RESET => {
+                    let at = *reset_at.get_or_insert(frames + RESET_DELAY_FRAMES);
+                    if frames >= at {
+                        nes.reset();
+                        reset_at = None;
+                    }
+                }
+                0 => {
+                    break TestStatus::Passed;
+                }
+                code if code < STATUS_RUNNING => {
+                    break TestStatus::Failed(code);
+                }
+                _ => {}
+            }
+        }
+        if frames >= max_frames {
+            break TestStatus::TimedOut;
+        }
+
+        nes.run_frame();
+        frames += 1;
+    };
+
+    TestReport {
+        status,
+        message: if has_signature(nes) { read_message(nes) } else { String::new() },
+        frames,
+    }
+}
+
+fn has_signature(nes: &Nes) -> bool {
+    SIGNATURE.iter()
+        .enumerate()
+        .all(|(i, &byte)| nes.peek(SIGNATURE_ADDRESS + (i as u16)) == byte)
+}
+
+fn read_message(nes: &Nes) -> String {
+    let bytes: Vec<u8> = (TEXT_ADDRESS..=TEXT_END)
+        .map(|address| nes.peek(address))
+        .take_while(|&byte| byte != 0)
+        .collect();
+    String::from_utf8_lossy(&bytes).trim_end().to_string()
+}
@@ -0,0 +1,139 @@
+//! Frame sequencer da APU: tempos dos modos de 4 e 5 passos, IRQ do frame counter,
+//! length counters e sweep.
+
+mod common;
+
+use common::nrom;
+use nes_core::Nes;
+
+fn nes() -> Nes {
+    let mut nes = Nes::from_rom_bytes(&nrom(&[], false)).unwrap();
+    nes.start_at(0x8000);
+    nes
+}
+
+fn write(nes: &mut Nes, address: u16, value: u8) {
+    nes.cpu_mut().bus.write(address, value);
+}
+
+fn status(nes: &mut Nes) -> u8 {
+    nes.cpu_mut().bus.read(0x4015)
+}
+
+/// Liga o pulse 1 com o length counter em 2 (índice 3 da tabela) e escreve `mode` em $4017.
+fn pulse_with_length_two(mode: u8) -> Nes {
+    let mut nes = nes();
+    write(&mut nes, 0x4015, 0x01);
+    write(&mut nes, 0x4003, 0x18);
+    write(&mut nes, 0x4017, mode);
+    nes
+}
+
+/// Ciclos até `condition` valer, contados a partir de agora.
+fn cycles_until(nes: &mut Nes, condition: impl Fn(&mut Nes) -> bool) -> u64 {
+    let start = nes.cycles();
+    while !condition(nes) {
+        nes.tick();
+        assert!(nes.cycles() - start < 100_000, "condition never met");
+    }
+    nes.cycles() - start
+}
+
+#[test]
+fn four_step_mode_sets_frame_irq() {
+    let mut nes = pulse_with_length_two(0x00);
+    assert_eq!(cycles_until(&mut nes, |nes| nes.cpu().bus.irq_line()), 29828);
+
+    // A flag fica ligada por três ciclos; a leitura de $4015 a limpa
+    nes.tick();
+    nes.tick();
+    assert_eq!(status(&mut nes) & 0x40, 0x40);
+    assert_eq!(status(&mut nes) & 0x40, 0x00);
+    assert!(!nes.cpu().bus.irq_line());
+
+    // A sequência recomeça e o IRQ volta depois de mais um período completo
+    assert_eq!(cycles_until(&mut nes, |nes| nes.cpu().bus.irq_line()), 29828);
+}
+
+#[test]
+fn four_step_mode_half_frames_clock_length_counter() {
+    // Half frames em 14913 e 29829: o length counter chega a 0 no segundo
+    let mut nes = pulse_with_length_two(0x00);
+    assert_eq!(cycles_until(&mut nes, |nes| status(nes) & 0x01 == 0), 29829);
+}
+
+#[test]
+fn five_step_mode_clocks_half_frame_on_write_and_has_no_irq() {
+    // A escrita gera um half frame imediato; o próximo vem em 14913
+    let mut nes = pulse_with_length_two(0x80);
+    assert_eq!(cycles_until(&mut nes, |nes| status(nes) & 0x01 == 0), 14913);
+
+    for _ in 0..2 * 37282 {
+        nes.tick();
+        assert!(!nes.cpu().bus.irq_line());
+    }
+}
+
+#[test]
+fn irq_inhibit_blocks_and_clears_frame_irq() {
+    let mut nes = pulse_with_length_two(0x00);
+    cycles_until(&mut nes, |nes| nes.cpu().bus.irq_line());
+    write(&mut nes, 0x4017, 0x40);
+    assert!(!nes.cpu().bus.irq_line());
+
+    for _ in 0..2 * 29830 {
+        nes.tick();
+    }
+    assert_eq!(status(&mut nes) & 0x40, 0x00);
+}
+
+#[test]
+fn length_counter_halt() {
+    // Bit 5 de $4000 trava o length counter
+    let mut nes = nes();
+    write(&mut nes, 0x4015, 0x01);
+    write(&mut nes, 0x4000, 0x20);
+    write(&mut nes, 0x4003, 0x18);
+    write(&mut nes, 0x4017, 0x40);
+    for _ in 0..3 * 29830 {
+        nes.tick();
+    }
+    assert_eq!(status(&mut nes) & 0x01, 0x01);
+
+    // Soltando o halt, os próximos dois half frames zeram o contador
+    write(&mut nes, 0x4000, 0x00);
+    for _ in 0..29830 {
+        nes.tick();
+    }
+    assert_eq!(status(&mut nes) & 0x01, 0x00);
+}
+
+#[test]
+fn pulse_one_sweep_negates_with_ones_complement() {
+    let mut nes = nes();
+    write(&mut nes, 0x4015, 0x03);
+    for (sweep, timer_low, timer_high) in [(0x4001, 0x4002, 0x4003), (0x4005, 0x4006, 0x4007)] {
+        write(&mut nes, timer_low, 0x00);
+        write(&mut nes, timer_high, 0x19); // período $100
+        write(&mut nes, sweep, 0x89); // ligado, período 0, negate, shift 1
+    }
+    write(&mut nes, 0x4017, 0x40);
+    for _ in 0..14913 {
+        nes.tick();
+    }
+
+    // $100 - ($100 >> 1): o pulse 1 subtrai um a mais
+    assert_eq!(nes.cpu().bus.apu.pulse_period(1), 0x7f);
+    assert_eq!(nes.cpu().bus.apu.pulse_period(2), 0x80);
+}
+
+#[test]
+fn sweep_adds_without_negate() {
+    let mut nes = nes();
+    write(&mut nes, 0x4015, 0x01);
+    write(&mut nes, 0x4002, 0x00);
+    write(&mut nes, 0x4003, 0x19);
+    write(&mut nes, 0x4001, 0x82); // ligado, período 0, shift 2
+    write(&mut nes, 0x4017, 0xc0);
+    assert_eq!(nes.cpu().bus.apu.pulse_period(1), 0x140);
+}
@@ -0,0 +1,31 @@
+//! ROMs montadas à mão para os testes de integração.
+// Cada arquivo de teste usa só parte dos helpers
+#![allow(dead_code)]
+
+/// Imagem iNES com o mapper, PRG (múltiplo de 16 KB) e CHR (múltiplo de 8 KB; vazio =
+/// CHR-RAM) indicados. Os vetores ficam por conta de quem monta o PRG.
+pub fn ines(mapper: u8, flags6: u8, prg: &[u8], chr: &[u8]) -> Vec<u8> {
+    let mut rom = vec![
+        b'N',
+        b'E',
+        b'S',
+        0x1a,
+        (prg.len() / 0x4000) as u8,
+        (chr.len() / 0x2000) as u8,
+        (mapper << 4) | flags6,
+        mapper & 0xf0
+    ];
+    rom.resize(16, 0);
+    rom.extend_from_slice(prg);
+    rom.extend_from_slice(chr);
+    rom
+}
+
+/// NROM-128 com `program` em $8000 e os três vetores apontando para ele; `battery`
+/// liga o bit de PRG-RAM com bateria no cabeçalho.
+pub fn nrom(program: &[u8], battery: bool) -> Vec<u8> {
+    let mut prg = vec![0xEA; 0x4000];
+    prg[..program.len()].copy_from_slice(program);
+    prg[0x3ffa..].copy_from_slice(&[0x00, 0x80, 0x00, 0x80, 0x00, 0x80]);
+    ines(0, if battery { 0x02 } else { 0x00 }, &prg, &vec![0; 0x2000])
+}
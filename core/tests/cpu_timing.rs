@@ -0,0 +1,66 @@
+//! Ciclos por instrução: penalidades de página cruzada e de desvio tomado.
+
+mod common;
+
+use common::ines;
+use nes_core::Nes;
+
+/// Monta um NROM com cada trecho de `code` no seu endereço e o reset em $8000, executa
+/// `count` instruções e retorna os ciclos gastos em cada uma.
+fn cycles(code: &[(u16, &[u8])], count: usize) -> Vec<u64> {
+    let mut prg = vec![0xEA; 0x4000];
+    for (address, bytes) in code {
+        let start = (*address as usize) - 0x8000;
+        prg[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+    prg[0x3ffa..].copy_from_slice(&[0x00, 0x80, 0x00, 0x80, 0x00, 0x80]);
+    let mut nes = Nes::from_rom_bytes(&ines(0, 0, &prg, &vec![0; 0x2000])).unwrap();
+    nes.start_at(0x8000);
+
+    (0..count)
+        .map(|_| {
+            let before = nes.cycles();
+            nes.step_instruction();
+            nes.cycles() - before
+        })
+        .collect()
+}
+
+#[test]
+fn absolute_indexed_page_cross() {
+    let program = [
+        0xa2, 0x01, // LDX #$01
+        0xbd, 0xff, 0x80, // LDA $80FF,X: cruza a página
+        0xbd, 0x00, 0x80, // LDA $8000,X
+        0x9d, 0xff, 0x02, // STA $02FF,X: escrita sempre paga o ciclo extra
+        0xde, 0xff, 0x02, // DEC $02FF,X
+    ];
+    assert_eq!(cycles(&[(0x8000, &program)], 5), [2, 5, 4, 5, 7]);
+}
+
+#[test]
+fn indirect_indexed_page_cross() {
+    let program = [
+        0xa9, 0xff, 0x85, 0x10, 0xa9, 0x02, 0x85, 0x11, // ($10) = $02FF
+        0xa0, 0x01, // LDY #$01
+        0xb1, 0x10, // LDA ($10),Y: cruza a página
+        0xa0, 0x00, // LDY #$00
+        0xb1, 0x10, // LDA ($10),Y
+        0x91, 0x10, // STA ($10),Y
+    ];
+    assert_eq!(cycles(&[(0x8000, &program)], 9), [2, 3, 2, 3, 2, 6, 2, 5, 6]);
+}
+
+#[test]
+fn branch_taken_and_page_cross() {
+    let start = [
+        0x18, // CLC
+        0xb0, 0x00, // BCS: não tomado
+        0x90, 0x00, // BCC: tomado, mesma página
+        0x4c, 0xfc, 0x80, // JMP $80FC
+    ];
+    let forward = [0x90, 0x02]; // BCC $8100: tomado, cruza a página
+    let backward = [0x90, 0xfc]; // BCC $80FE: tomado, cruza de volta
+    let code: [(u16, &[u8]); 3] = [(0x8000, &start), (0x80fc, &forward), (0x8100, &backward)];
+    assert_eq!(cycles(&code, 6), [2, 2, 3, 3, 4, 4]);
+}
@@ -0,0 +1,101 @@
+//! ADC/SBC em modo decimal nas variantes com BCD, com os vetores do tutorial de
+//! Bruce Clark no 6502.org ("Decimal Mode").
+
+mod common;
+
+use common::nrom;
+use nes_core::cpu::CpuVariant;
+use nes_core::Nes;
+
+const CARRY: u8 = 0x01;
+const ZERO: u8 = 0x02;
+const OVERFLOW: u8 = 0x40;
+const NEGATIVE: u8 = 0x80;
+
+/// Executa SED, CLC/SEC, LDA #`acc` e ADC/SBC #`value` (`opcode`) na `variant` e retorna
+/// A, P e os ciclos da última instrução.
+fn run(variant: CpuVariant, opcode: u8, carry: bool, acc: u8, value: u8) -> (u8, u8, u64) {
+    let program = [0xf8, if carry { 0x38 } else { 0x18 }, 0xa9, acc, opcode, value];
+    let mut nes = Nes::from_rom_bytes(&nrom(&program, false)).unwrap();
+    nes.cpu_mut().variant = variant;
+    nes.start_at(0x8000);
+    for _ in 0..3 {
+        nes.step_instruction();
+    }
+    let before = nes.cycles();
+    nes.step_instruction();
+    let registers = &nes.cpu().registers;
+    (registers.acc, registers.status_register, nes.cycles() - before)
+}
+
+fn adc(variant: CpuVariant, acc: u8, value: u8) -> (u8, u8, u64) {
+    run(variant, 0x69, false, acc, value)
+}
+
+fn sbc(variant: CpuVariant, acc: u8, value: u8) -> (u8, u8, u64) {
+    run(variant, 0xe9, true, acc, value)
+}
+
+fn flags(status: u8) -> u8 {
+    status & (CARRY | ZERO | OVERFLOW | NEGATIVE)
+}
+
+#[test]
+fn nmos_adc_flags_come_from_intermediate_and_binary_results() {
+    // 99 + 01 = 00 com carry; Z vem do binário ($9A) e N do intermediário ($A0)
+    let (acc, status, cycles) = adc(CpuVariant::Nmos6502, 0x99, 0x01);
+    assert_eq!((acc, flags(status), cycles), (0x00, CARRY | NEGATIVE, 2));
+
+    // 24 + 56 = 80: N e V ligados como numa soma binária com sinal
+    let (acc, status, _) = adc(CpuVariant::Nmos6502, 0x24, 0x56);
+    assert_eq!((acc, flags(status)), (0x80, OVERFLOW | NEGATIVE));
+
+    // 93 + 82 = 75 com carry e V ligado
+    let (acc, status, _) = adc(CpuVariant::Nmos6502, 0x93, 0x82);
+    assert_eq!((acc, flags(status)), (0x75, CARRY | OVERFLOW));
+}
+
+#[test]
+fn nmos_adc_with_invalid_bcd() {
+    // Nibbles acima de 9 ainda recebem o ajuste de +6
+    let (acc, status, _) = adc(CpuVariant::Nmos6502, 0x00, 0x0f);
+    assert_eq!((acc, flags(status)), (0x15, 0));
+
+    let (acc, status, _) = adc(CpuVariant::Nmos6502, 0x0f, 0x01);
+    assert_eq!((acc, flags(status)), (0x16, 0));
+
+    // $FF + $FF: intermediário $F4 (N), binário $FE (Z limpa), resultado $54 com carry
+    let (acc, status, _) = adc(CpuVariant::Nmos6502, 0xff, 0xff);
+    assert_eq!((acc, flags(status)), (0x54, CARRY | NEGATIVE));
+}
+
+#[test]
+fn nmos_sbc_flags_come_from_binary_result() {
+    // 00 - 21 = 79 com borrow; N vem do binário ($DF)
+    let (acc, status, cycles) = sbc(CpuVariant::Nmos6502, 0x00, 0x21);
+    assert_eq!((acc, flags(status), cycles), (0x79, NEGATIVE, 2));
+
+    let (acc, status, _) = sbc(CpuVariant::Nmos6502, 0x46, 0x12);
+    assert_eq!((acc, flags(status)), (0x34, CARRY));
+
+    let (acc, status, _) = sbc(CpuVariant::Nmos6502, 0x21, 0x21);
+    assert_eq!((acc, flags(status)), (0x00, CARRY | ZERO));
+}
+
+#[test]
+fn cmos_decimal_flags_are_valid_and_cost_a_cycle() {
+    let (acc, status, cycles) = adc(CpuVariant::Cmos65C02, 0x99, 0x01);
+    assert_eq!((acc, flags(status), cycles), (0x00, CARRY | ZERO, 3));
+
+    let (acc, status, _) = adc(CpuVariant::Cmos65C02, 0x24, 0x56);
+    assert_eq!((acc, flags(status)), (0x80, OVERFLOW | NEGATIVE));
+
+    let (acc, status, cycles) = sbc(CpuVariant::Cmos65C02, 0x00, 0x21);
+    assert_eq!((acc, flags(status), cycles), (0x79, 0, 3));
+}
+
+#[test]
+fn ricoh_ignores_decimal_flag() {
+    let (acc, status, cycles) = adc(CpuVariant::Ricoh2A03, 0x99, 0x01);
+    assert_eq!((acc, flags(status), cycles), (0x9a, NEGATIVE, 2));
+}
@@ -0,0 +1,72 @@
+//! DMC: ciclos roubados da CPU pelo memory reader, loop do sample e IRQ de fim de sample.
+
+mod common;
+
+use common::nrom;
+use nes_core::Nes;
+
+/// Toca um sample de 1 byte em $C400 na taxa mais rápida (54 ciclos por bit), com as
+/// flags de IRQ/loop de `control` em $4010. A CPU fica executando NOPs.
+fn play(control: u8) -> Nes {
+    let mut nes = Nes::from_rom_bytes(&nrom(&[], false)).unwrap();
+    nes.start_at(0x8000);
+    let bus = &mut nes.cpu_mut().bus;
+    bus.write(0x4017, 0x40); // sem o IRQ do frame counter
+    bus.write(0x4010, control | 0x0f);
+    bus.write(0x4012, 0x10);
+    bus.write(0x4013, 0x00);
+    bus.write(0x4015, 0x10);
+    nes
+}
+
+/// Ciclos de cada uma das próximas `count` instruções (NOPs de 2 ciclos).
+fn nop_cycles(nes: &mut Nes, count: usize) -> Vec<u64> {
+    (0..count)
+        .map(|_| {
+            let before = nes.cycles();
+            nes.step_instruction();
+            nes.cycles() - before
+        })
+        .collect()
+}
+
+fn status(nes: &mut Nes) -> u8 {
+    nes.cpu_mut().bus.read(0x4015)
+}
+
+#[test]
+fn sample_fetch_stalls_cpu_for_four_cycles() {
+    let mut nes = play(0x00);
+    assert_eq!(status(&mut nes) & 0x10, 0x10);
+    assert_eq!(nop_cycles(&mut nes, 3), [6, 2, 2]);
+    assert_eq!(status(&mut nes) & 0x10, 0x00);
+
+    // Sem loop nada mais é buscado
+    assert!(nop_cycles(&mut nes, 2000).iter().all(|&cycles| cycles == 2));
+}
+
+#[test]
+fn sample_end_sets_irq_flag() {
+    let mut nes = play(0x80);
+    assert!(!nes.cpu().bus.irq_line());
+    nop_cycles(&mut nes, 1);
+    assert!(nes.cpu().bus.irq_line());
+    assert_eq!(status(&mut nes) & 0x90, 0x80);
+
+    // A leitura não limpa o IRQ do DMC; a escrita em $4015 limpa
+    assert_eq!(status(&mut nes) & 0x80, 0x80);
+    nes.cpu_mut().bus.write(0x4015, 0x00);
+    assert_eq!(status(&mut nes) & 0x80, 0x00);
+    assert!(!nes.cpu().bus.irq_line());
+}
+
+#[test]
+fn looping_sample_restarts_without_irq() {
+    let mut nes = play(0xc0);
+    // O byte é consumido a cada 8 bits de 54 ciclos; cada recomeço busca de novo
+    let cycles = nop_cycles(&mut nes, 54 * 8 * 10 / 2);
+    let stalls = cycles.iter().filter(|&&cycles| cycles == 6).count();
+    assert!((10..=11).contains(&stalls), "{stalls} fetches");
+    assert_eq!(status(&mut nes) & 0x90, 0x10);
+    assert!(!nes.cpu().bus.irq_line());
+}
@@ -0,0 +1,54 @@
+//! Protocolo serial dos controles em $4016/$4017.
+
+mod common;
+
+use common::nrom;
+use nes_core::input::Key;
+use nes_core::Nes;
+
+fn nes() -> Nes {
+    Nes::from_rom_bytes(&nrom(&[], false)).unwrap()
+}
+
+/// Faz o strobe (1 e depois 0) e lê `count` bits da porta `port`.
+fn read_bits(nes: &mut Nes, port: u16, count: usize) -> Vec<u8> {
+    let bus = &mut nes.cpu_mut().bus;
+    bus.write(0x4016, 1);
+    bus.write(0x4016, 0);
+    (0..count).map(|_| bus.read(port) & 1).collect()
+}
+
+#[test]
+fn reads_buttons_in_order_then_ones() {
+    let mut nes = nes();
+    nes.set_button(0, Key::A, true);
+    nes.set_button(0, Key::Start, true);
+    nes.set_button(0, Key::Right, true);
+
+    assert_eq!(read_bits(&mut nes, 0x4016, 12), [1, 0, 0, 1, 0, 0, 0, 1, 1, 1, 1, 1]);
+}
+
+#[test]
+fn strobe_held_high_keeps_returning_a() {
+    let mut nes = nes();
+    nes.set_button(0, Key::A, true);
+    nes.set_button(0, Key::B, true);
+    let bus = &mut nes.cpu_mut().bus;
+    bus.write(0x4016, 1);
+    assert_eq!((0..4).map(|_| bus.read(0x4016) & 1).collect::<Vec<_>>(), [1, 1, 1, 1]);
+
+    nes.set_button(0, Key::A, false);
+    let bus = &mut nes.cpu_mut().bus;
+    assert_eq!(bus.read(0x4016) & 1, 0);
+}
+
+#[test]
+fn second_port_reads_player_two() {
+    let mut nes = nes();
+    nes.set_button(0, Key::A, true);
+    nes.set_button(1, Key::B, true);
+    nes.set_button(1, Key::Up, true);
+
+    assert_eq!(read_bits(&mut nes, 0x4017, 9), [0, 1, 0, 0, 1, 0, 0, 0, 1]);
+    assert_eq!(read_bits(&mut nes, 0x4016, 2), [1, 0]);
+}
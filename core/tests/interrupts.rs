@@ -0,0 +1,125 @@
+//! Linhas de interrupção: NMI por borda, IRQ por nível e a latência de CLI/SEI/PLP.
+
+mod common;
+
+use common::ines;
+use nes_core::Nes;
+
+/// NROM-128 com `program` em $8000 (reset), `nmi` em $8100 e `irq` em $8200.
+fn rom(program: &[u8], nmi: &[u8], irq: &[u8]) -> Nes {
+    let mut prg = vec![0xEA; 0x4000];
+    prg[..program.len()].copy_from_slice(program);
+    prg[0x100..0x100 + nmi.len()].copy_from_slice(nmi);
+    prg[0x200..0x200 + irq.len()].copy_from_slice(irq);
+    prg[0x3ffa..].copy_from_slice(&[0x00, 0x81, 0x00, 0x80, 0x00, 0x82]);
+    Nes::from_rom_bytes(&ines(0, 0, &prg, &vec![0; 0x2000])).unwrap()
+}
+
+/// Liga o NMI no PPUCTRL e fica em `JMP *`.
+const ENABLE_NMI: [u8; 8] = [0xa9, 0x80, 0x8d, 0x00, 0x20, 0x4c, 0x05, 0x80];
+
+fn nmi_count(handler: &[u8]) -> u8 {
+    let mut nes = rom(&ENABLE_NMI, handler, &[0x40]);
+    for _ in 0..10 {
+        nes.run_frame();
+    }
+    nes.ram()[0x10]
+}
+
+#[test]
+fn nmi_fires_once_per_vblank() {
+    // INC $10; RTI
+    assert_eq!(nmi_count(&[0xe6, 0x10, 0x40]), 10);
+}
+
+#[test]
+fn status_read_drops_nmi_line_before_reenabling() {
+    // INC $10; LDA $2002; LDA #0; STA $2000; LDA #$80; STA $2000; RTI
+    let handler = [
+        0xe6, 0x10, 0xad, 0x02, 0x20, 0xa9, 0x00, 0x8d, 0x00, 0x20, 0xa9, 0x80, 0x8d, 0x00,
+        0x20, 0x40,
+    ];
+    assert_eq!(nmi_count(&handler), 10);
+}
+
+#[test]
+fn reenabling_nmi_during_vblank_raises_a_new_edge() {
+    // INC $10; LDA #0; STA $2000; LDA #$80; STA $2000; RTI, sem ler $2002: cada escrita
+    // de $80 com a VBlank ainda ligada gera outra borda
+    let handler = [
+        0xe6, 0x10, 0xa9, 0x00, 0x8d, 0x00, 0x20, 0xa9, 0x80, 0x8d, 0x00, 0x20, 0x40,
+    ];
+    let mut nes = rom(&ENABLE_NMI, &handler, &[0x40]);
+    nes.run_frame();
+    nes.run_frame();
+    assert!(nes.ram()[0x10] > 2);
+
+    // Rescrever $80 com o bit já ligado não gera borda: INC $10; LDA #$80; STA $2000; RTI
+    assert_eq!(nmi_count(&[0xe6, 0x10, 0xa9, 0x80, 0x8d, 0x00, 0x20, 0x40]), 10);
+}
+
+/// Programa que arma o IRQ do frame counter da APU (modo de 4 passos) e espera com a flag
+/// I ligada; `code` fica em $8010 para o teste pular para lá com o IRQ já pendente.
+fn pending_irq(code: &[u8], handler: &[u8]) -> Nes {
+    let mut program = vec![0xEA; 0x10 + code.len()];
+    program[..8].copy_from_slice(&[0xa9, 0x00, 0x8d, 0x17, 0x40, 0x4c, 0x05, 0x80]);
+    program[0x10..].copy_from_slice(code);
+    let mut nes = rom(&program, &[0x40], handler);
+    while !nes.cpu().bus.irq_line() {
+        nes.step_instruction();
+    }
+    nes.cpu_mut().registers.program_counter = 0x8010;
+    nes
+}
+
+/// Endereços das próximas `count` instruções (ou entradas em interrupção).
+fn trace_pc(nes: &mut Nes, count: usize) -> Vec<u16> {
+    (0..count)
+        .map(|_| {
+            nes.step_instruction();
+            nes.cpu().registers.program_counter
+        })
+        .collect()
+}
+
+#[test]
+fn irq_is_level_triggered() {
+    // Sem reconhecer o IRQ, o handler é reentrado logo após o RTI: INC $10; RTI
+    let mut nes = pending_irq(&[0x58, 0x4c, 0x11, 0x80], &[0xe6, 0x10, 0x40]); // CLI; JMP *
+    for _ in 0..20 {
+        nes.step_instruction();
+    }
+    assert!(nes.ram()[0x10] >= 5);
+
+    // Lendo $4015 a linha cai: INC $10; LDA $4015; RTI
+    let mut nes = pending_irq(&[0x58, 0x4c, 0x11, 0x80], &[0xe6, 0x10, 0xad, 0x15, 0x40, 0x40]);
+    for _ in 0..20 {
+        nes.step_instruction();
+    }
+    assert_eq!(nes.ram()[0x10], 1);
+    assert!(!nes.cpu().bus.irq_line());
+}
+
+#[test]
+fn cli_takes_effect_after_the_next_instruction() {
+    // CLI; NOP; NOP: o IRQ entra depois do primeiro NOP
+    let mut nes = pending_irq(&[0x58, 0xea, 0xea], &[0x40]);
+    assert_eq!(trace_pc(&mut nes, 3), [0x8011, 0x8012, 0x8200]);
+}
+
+#[test]
+fn cli_sei_still_takes_the_irq() {
+    // CLI; SEI: o polling do SEI ainda vê a flag I limpa e o IRQ entra depois dele,
+    // empilhando P com I ligada
+    let mut nes = pending_irq(&[0x58, 0x78, 0xea], &[0x40]);
+    assert_eq!(trace_pc(&mut nes, 3), [0x8011, 0x8012, 0x8200]);
+    let stack = 0x100 + nes.cpu().registers.stack_pointer as usize + 1;
+    assert_eq!(nes.ram()[stack] & 0x04, 0x04);
+}
+
+#[test]
+fn plp_takes_effect_after_the_next_instruction() {
+    // LDA #0; PHA; PLP; NOP; NOP
+    let mut nes = pending_irq(&[0xa9, 0x00, 0x48, 0x28, 0xea, 0xea], &[0x40]);
+    assert_eq!(trace_pc(&mut nes, 5), [0x8012, 0x8013, 0x8014, 0x8015, 0x8200]);
+}
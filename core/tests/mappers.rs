@@ -0,0 +1,372 @@
+//! Chaveamento de bancos dos mappers, com ROMs montadas à mão.
+
+mod common;
+
+use common::ines;
+use nes_core::Nes;
+
+/// PRG com `banks` bancos de 16 KB; cada banco tem o próprio número em todos os bytes,
+/// e `program` fica no começo do último banco ($C000), com os vetores apontando para ele.
+fn banked_prg(banks: usize, program: &[u8]) -> Vec<u8> {
+    let mut prg: Vec<u8> = (0..banks).flat_map(|bank| vec![bank as u8; 0x4000]).collect();
+    let last = (banks - 1) * 0x4000;
+    prg[last..last + program.len()].copy_from_slice(program);
+    prg[last + 0x3ffa..].copy_from_slice(&[0x00, 0xc0, 0x00, 0xc0, 0x00, 0xc0]);
+    prg
+}
+
+fn run(rom: &[u8]) -> Nes {
+    let mut nes = Nes::from_rom_bytes(rom).expect("invalid test ROM");
+    nes.run_frame();
+    nes
+}
+
+#[test]
+fn uxrom_switches_low_bank_and_fixes_last() {
+    let program = [
+        0xa9, 0x02, 0x8d, 0x00, 0x80, // LDA #$02; STA $8000
+        0xad, 0x00, 0x80, 0x85, 0x00, // LDA $8000; STA $00
+        0xa9, 0x01, 0x8d, 0xff, 0xff, // LDA #$01; STA $FFFF
+        0xad, 0xff, 0xbf, 0x85, 0x01, // LDA $BFFF; STA $01
+        0x4c, 0x14, 0xc0, // JMP *
+    ];
+    let nes = run(&ines(2, 0, &banked_prg(8, &program), &[]));
+    assert_eq!(nes.ram()[0], 2);
+    assert_eq!(nes.ram()[1], 1);
+    assert_eq!(nes.peek(0xc100), 7);
+}
+
+/// Escreve `value` num registrador do MMC1 pela porta serial: 5 escritas, bit 0 primeiro.
+fn mmc1_write(address: u16, value: u8) -> Vec<u8> {
+    (0..5).flat_map(|bit| sta((value >> bit) & 1, address)).collect()
+}
+
+/// MMC1 com 8 bancos de 4 KB de CHR, cada um preenchido com o próprio número. O programa
+/// lê $0000 e $1000 pela PPU no modo de 8 KB (banco 5, com o bit baixo ignorado) e no de
+/// 4 KB (bancos 6 e 1), guardando em $00-$03.
+#[test]
+fn mmc1_serial_writes_switch_chr_banks() {
+    let mut program = [
+        mmc1_write(0x8000, 0x0c),
+        mmc1_write(0xa000, 0x05),
+        vec![0xad, 0x02, 0x20],
+        ppu_read(0x0000, 0x00),
+        ppu_read(0x1000, 0x01),
+        mmc1_write(0x8000, 0x1c),
+        mmc1_write(0xa000, 0x06),
+        mmc1_write(0xc000, 0x01),
+        ppu_read(0x0000, 0x02),
+        ppu_read(0x1000, 0x03),
+    ].concat();
+    let end = 0xc000 + program.len() as u16;
+    program.extend([0x4c, end as u8, (end >> 8) as u8]); // JMP *
+
+    let chr: Vec<u8> = (0..8u8).flat_map(|bank| vec![bank; 0x1000]).collect();
+    let nes = run(&ines(1, 0, &banked_prg(2, &program), &chr));
+    assert_eq!(nes.ram()[..4], [4, 5, 6, 1]);
+}
+
+/// CNROM com 32 KB de PRG e 4 bancos de CHR, cada um preenchido com o próprio número.
+/// O programa seleciona o banco 2 e depois tenta o 3 escrevendo sobre um byte $01 da
+/// ROM, guardando em $00 e $01 o que lê da pattern table.
+fn cnrom(submapper: u8) -> Vec<u8> {
+    let read_chr = [
+        0xa9, 0x00, 0x8d, 0x06, 0x20, 0x8d, 0x06, 0x20, // PPUADDR = $0000
+        0xad, 0x07, 0x20, 0xad, 0x07, 0x20, // LDA $2007 (buffer); LDA $2007
+    ];
+    let mut program = vec![0xa9, 0x02, 0x8d, 0x02, 0x81, 0xad, 0x02, 0x20]; // STA $8102; LDA $2002
+    program.extend(read_chr);
+    program.extend([0x85, 0x00, 0xa9, 0x03, 0x8d, 0x01, 0x81]); // STA $00; LDA #$03; STA $8101
+    program.extend(read_chr);
+    program.extend([0x85, 0x01, 0x4c, 0x2d, 0x80]); // STA $01; JMP *
+
+    let mut prg = vec![0xEA; 0x8000];
+    prg[..program.len()].copy_from_slice(&program);
+    prg[0x100..0x104].copy_from_slice(&[0, 1, 2, 3]);
+    prg[0x7ffa..].copy_from_slice(&[0x00, 0x80, 0x00, 0x80, 0x00, 0x80]);
+    let chr: Vec<u8> = (0..4u8).flat_map(|bank| vec![bank; 0x2000]).collect();
+
+    let mut rom = ines(3, 0, &prg, &chr);
+    rom[7] |= 0x08; // NES 2.0
+    rom[8] = submapper << 4;
+    rom
+}
+
+#[test]
+fn cnrom_switches_chr_with_bus_conflicts() {
+    let nes = run(&cnrom(0));
+    assert_eq!(nes.ram()[0], 2);
+    assert_eq!(nes.ram()[1], 1);
+}
+
+#[test]
+fn cnrom_without_bus_conflicts() {
+    let nes = run(&cnrom(1));
+    assert_eq!(nes.ram()[0], 2);
+    assert_eq!(nes.ram()[1], 3);
+}
+
+/// MMC3 com 8 bancos de 8 KB de PRG (cada um preenchido com o próprio número) e
+/// CHR-RAM. O programa testa os dois modos de PRG, guarda as leituras em $00-$02, arma
+/// o IRQ para 10 scanlines e escreve `mask` em PPUMASK; o handler conta em $03 e desarma.
+fn mmc3(mask: u8) -> Vec<u8> {
+    let program = [
+        0xa9, 0x40, 0x8d, 0x17, 0x40, // sem o IRQ do frame counter da APU
+        0xa9, 0x06, 0x8d, 0x00, 0x80, 0xa9, 0x03, 0x8d, 0x01, 0x80, // R6 = 3
+        0xad, 0x00, 0x80, 0x85, 0x00, // LDA $8000; STA $00
+        0xa9, 0x46, 0x8d, 0x00, 0x80, // modo de PRG 1
+        0xad, 0x00, 0x80, 0x85, 0x01, // LDA $8000; STA $01
+        0xad, 0x00, 0xc0, 0x85, 0x02, // LDA $C000; STA $02
+        0xa9, 0x0a, 0x8d, 0x00, 0xc0, 0x8d, 0x01, 0xc0, 0x8d, 0x01, 0xe0, // latch 10, reload, liga
+        0xa9, 0x08, 0x8d, 0x00, 0x20, // sprites em $1000
+        0xa9, mask, 0x8d, 0x01, 0x20, // PPUMASK
+        0x58, 0x4c, 0x39, 0xe0, // CLI; JMP *
+        0xe6, 0x03, 0x8d, 0x00, 0xe0, 0x40, // IRQ: INC $03; STA $E000; RTI
+    ];
+    let mut prg: Vec<u8> = (0..8u8).flat_map(|bank| vec![bank; 0x2000]).collect();
+    prg[0xe000..0xe000 + program.len()].copy_from_slice(&program);
+    prg[0xfffa..].copy_from_slice(&[0x41, 0xe0, 0x00, 0xe0, 0x3c, 0xe0]);
+    ines(4, 0, &prg, &[])
+}
+
+#[test]
+fn mmc3_prg_modes_and_scanline_irq() {
+    let mut nes = run(&mmc3(0x18));
+    assert_eq!(nes.ram()[..3], [3, 6, 3]);
+    assert_eq!(nes.ram()[3], 1);
+    // O handler desarmou o IRQ
+    nes.run_frame();
+    assert_eq!(nes.ram()[3], 1);
+}
+
+#[test]
+fn mmc3_irq_needs_rendering() {
+    let nes = run(&mmc3(0x00));
+    assert_eq!(nes.ram()[3], 0);
+}
+
+/// AxROM com 4 bancos de 32 KB, cada um preenchido com o próprio número e com o mesmo
+/// programa em $8000 (o banco troca sob a CPU). Escreve $11 na tela baixa por $2000 e $22
+/// na alta por $2C00, e lê de volta por $2400 e $2000 com a outra tela selecionada.
+fn axrom() -> Vec<u8> {
+    let select = |value: u8| [0xa9, value, 0x8d, 0x00, 0x80];
+    let ppu_addr = |hi: u8| [0xa9, hi, 0x8d, 0x06, 0x20, 0xa9, 0x00, 0x8d, 0x06, 0x20];
+    let write = |value: u8| [0xa9, value, 0x8d, 0x07, 0x20];
+    let read = [0xad, 0x07, 0x20, 0xad, 0x07, 0x20]; // buffer; dado
+
+    let mut program = vec![0xad, 0x02, 0x20]; // LDA $2002
+    program.extend(select(0x02));
+    program.extend([0xad, 0x00, 0x90, 0x85, 0x00]); // LDA $9000; STA $00
+    program.extend(ppu_addr(0x20));
+    program.extend(write(0x11));
+    program.extend(select(0x12));
+    program.extend(ppu_addr(0x2c));
+    program.extend(write(0x22));
+    program.extend(select(0x02));
+    program.extend(ppu_addr(0x24));
+    program.extend(read);
+    program.extend([0x85, 0x01]); // STA $01
+    program.extend(select(0x12));
+    program.extend(ppu_addr(0x20));
+    program.extend(read);
+    program.extend([0x85, 0x02]); // STA $02
+    let end = 0x8000 + program.len() as u16;
+    program.extend([0x4c, end as u8, (end >> 8) as u8]); // JMP *
+
+    let mut prg = Vec::new();
+    for bank in 0..4u8 {
+        let mut data = vec![bank; 0x8000];
+        data[..program.len()].copy_from_slice(&program);
+        data[0x7ffa..].copy_from_slice(&[0x00, 0x80, 0x00, 0x80, 0x00, 0x80]);
+        prg.extend(data);
+    }
+    ines(7, 0, &prg, &[])
+}
+
+#[test]
+fn axrom_switches_prg_and_one_screen_mirroring() {
+    let nes = run(&axrom());
+    assert_eq!(nes.ram()[..3], [2, 0x11, 0x22]);
+}
+
+/// MMC5 com 16 bancos de 8 KB de PRG (cada um com o próprio número), `program` em $E000
+/// (último banco, fixo no power-up) seguido de um `JMP *`, e `handler` em $F000, alvo
+/// dos vetores de NMI e IRQ.
+fn mmc5(program: &[u8], handler: &[u8], chr: &[u8]) -> Vec<u8> {
+    let mut program = program.to_vec();
+    let end = 0xe000 + program.len() as u16;
+    program.extend([0x4c, end as u8, (end >> 8) as u8]);
+
+    let mut prg: Vec<u8> = (0..16u8).flat_map(|bank| vec![bank; 0x2000]).collect();
+    prg[0x1e000..0x1e000 + program.len()].copy_from_slice(&program);
+    prg[0x1f000..0x1f000 + handler.len()].copy_from_slice(handler);
+    prg[0x1fffa..].copy_from_slice(&[0x00, 0xf0, 0x00, 0xe0, 0x00, 0xf0]);
+    ines(5, 0, &prg, chr)
+}
+
+fn sta(value: u8, address: u16) -> [u8; 5] {
+    [0xa9, value, 0x8d, address as u8, (address >> 8) as u8]
+}
+
+/// LDA `address`; STA `zero_page`.
+fn copy(address: u16, zero_page: u8) -> [u8; 5] {
+    [0xad, address as u8, (address >> 8) as u8, 0x85, zero_page]
+}
+
+/// Aponta PPUADDR para `address`.
+fn ppu_addr(address: u16) -> Vec<u8> {
+    [sta((address >> 8) as u8, 0x2006), sta(address as u8, 0x2006)].concat()
+}
+
+/// Lê `address` pela PPU ($2007 duas vezes, por causa do buffer) e guarda em `zero_page`.
+fn ppu_read(address: u16, zero_page: u8) -> Vec<u8> {
+    [ppu_addr(address), vec![0xad, 0x07, 0x20], copy(0x2007, zero_page).to_vec()].concat()
+}
+
+#[test]
+fn mmc5_banking_multiplier_and_nametables() {
+    let program = [
+        // Multiplicador: $12 * $34 = $03A8
+        sta(0x12, 0x5205).to_vec(),
+        sta(0x34, 0x5206).to_vec(),
+        copy(0x5205, 0x00).to_vec(),
+        copy(0x5206, 0x01).to_vec(),
+        // Modo 3: ROM 3 em $8000
+        sta(0x83, 0x5114).to_vec(),
+        copy(0x8000, 0x02).to_vec(),
+        // Modo 1: banco de 16 KB 6-7 em $8000
+        sta(0x01, 0x5100).to_vec(),
+        sta(0x86, 0x5115).to_vec(),
+        copy(0xa000, 0x03).to_vec(),
+        // PRG-RAM em $8000 (a mesma de $6000), com a proteção liberada
+        sta(0x02, 0x5102).to_vec(),
+        sta(0x01, 0x5103).to_vec(),
+        sta(0x00, 0x5115).to_vec(),
+        sta(0x5a, 0x8005).to_vec(),
+        copy(0x6005, 0x04).to_vec(),
+        // ExRAM como RAM comum
+        sta(0x02, 0x5104).to_vec(),
+        sta(0x77, 0x5c10).to_vec(),
+        copy(0x5c10, 0x05).to_vec(),
+        // Slots: VRAM 0, VRAM 1, ExRAM, fill mode
+        sta(0xe4, 0x5105).to_vec(),
+        sta(0x42, 0x5106).to_vec(),
+        sta(0x02, 0x5107).to_vec(),
+        sta(0x00, 0x5104).to_vec(),
+        vec![0xad, 0x02, 0x20],
+        ppu_addr(0x2800),
+        sta(0x99, 0x2007).to_vec(),
+        ppu_read(0x2c00, 0x06),
+        ppu_read(0x2fc0, 0x07),
+        ppu_read(0x2800, 0x08),
+        sta(0x02, 0x5104).to_vec(),
+        copy(0x5c00, 0x09).to_vec(),
+    ].concat();
+    let nes = run(&mmc5(&program, &[0x40], &vec![0; 0x2000]));
+    assert_eq!(nes.ram()[..10], [0xa8, 0x03, 3, 7, 0x5a, 0x77, 0x42, 0xaa, 0x99, 0x99]);
+}
+
+#[test]
+fn mmc5_scanline_irq() {
+    let program = [
+        sta(0x40, 0x4017).to_vec(), // sem o IRQ do frame counter da APU
+        sta(20, 0x5203).to_vec(),
+        sta(0x80, 0x5204).to_vec(),
+        sta(0x08, 0x2001).to_vec(),
+        vec![0x58], // CLI
+    ].concat();
+    // Reconhece lendo $5204, guarda o status e conta
+    let handler = [copy(0x5204, 0x00).to_vec(), vec![0xe6, 0x01, 0x40]].concat();
+    let mut nes = run(&mmc5(&program, &handler, &vec![0; 0x2000]));
+    assert_eq!(nes.ram()[..2], [0xc0, 1]);
+    nes.run_frame();
+    assert_eq!(nes.ram()[1], 2);
+}
+
+#[test]
+fn mmc5_peek_has_no_side_effects() {
+    let program = [
+        sta(0x40, 0x4017).to_vec(),
+        sta(200, 0x5203).to_vec(),
+        sta(0x80, 0x5204).to_vec(),
+        sta(0x08, 0x2001).to_vec(),
+        vec![0x58],
+    ].concat();
+    let handler = [copy(0x5204, 0x00).to_vec(), vec![0xe6, 0x01, 0x40]].concat();
+    let mut nes = run(&mmc5(&program, &handler, &vec![0; 0x2000]));
+    assert_eq!(nes.ram()[1], 1);
+
+    // Um debugger lendo o vetor de NMI e o status a cada ciclo não pode reiniciar a
+    // detecção de frame nem reconhecer o IRQ
+    loop {
+        nes.peek(0xfffa);
+        nes.peek(0x5204);
+        nes.trace_line();
+        if nes.tick() {
+            break;
+        }
+    }
+    assert_eq!(nes.ram()[1], 2);
+}
+
+#[test]
+fn mmc5_vertical_split() {
+    // CHR: $0000-$0FFF vazio; em $1000 o tile 0 tem o plano baixo cheio (cor 1)
+    let mut chr = vec![0; 0x2000];
+    chr[0x1000..0x1008].fill(0xff);
+    let program = [
+        vec![0xad, 0x02, 0x20],
+        ppu_addr(0x3f00),
+        sta(0x0f, 0x2007).to_vec(), // fundo preto
+        sta(0x30, 0x2007).to_vec(), // cor 1 branca
+        // Divisão nos 16 tiles da esquerda, com o banco de 4 KB 1
+        sta(0x90, 0x5200).to_vec(),
+        sta(0x01, 0x5202).to_vec(),
+        ppu_addr(0x2000),
+        sta(0x0a, 0x2001).to_vec(),
+    ].concat();
+    let mut nes = run(&mmc5(&program, &[0x40], &chr));
+    nes.run_frame();
+    let pixel = |x: usize, y: usize| nes.framebuffer()[(y * 256 + x) * 3];
+    assert!(pixel(8, 100) > 200);
+    assert!(pixel(120, 100) > 200);
+    assert!(pixel(136, 100) < 50);
+    assert!(pixel(248, 100) < 50);
+}
+
+#[test]
+fn mmc5_extended_attributes() {
+    let mut chr = vec![0; 0x2000];
+    chr[0x1000..0x1008].fill(0xff);
+    let program = [
+        vec![0xad, 0x02, 0x20],
+        ppu_addr(0x3f00),
+        sta(0x0f, 0x2007).to_vec(),
+        sta(0x16, 0x2007).to_vec(), // paleta 0, cor 1: vermelha
+        ppu_addr(0x3f05),
+        sta(0x30, 0x2007).to_vec(), // paleta 1, cor 1: branca
+        // Tile da linha 12, coluna 1: banco de 4 KB 1 e paleta 1. A ExRAM é escrita
+        // no modo 2, já que nos modos 0 e 1 a CPU só escreve durante o render
+        sta(0x02, 0x5104).to_vec(),
+        sta(0x41, 0x5c00 + 12 * 32 + 1).to_vec(),
+        sta(0x01, 0x5104).to_vec(),
+        ppu_addr(0x2000),
+        sta(0x0a, 0x2001).to_vec(),
+    ].concat();
+    let mut nes = run(&mmc5(&program, &[0x40], &chr));
+    nes.run_frame();
+    let pixel = |x: usize, y: usize| nes.framebuffer()[(y * 256 + x) * 3];
+    assert_eq!(pixel(12, 100), 236);
+    assert_eq!(pixel(20, 100), 0);
+    assert_eq!(pixel(12, 90), 0);
+}
+
+#[test]
+fn rejects_header_without_prg_rom() {
+    for mapper in [0, 1, 2, 3, 4, 5, 7] {
+        let error = Nes::from_rom_bytes(&ines(mapper, 0, &[], &[])).err().expect("ROM accepted");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+    let error = Nes::from_rom_bytes(b"NES\x1a").err().expect("truncated header accepted");
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
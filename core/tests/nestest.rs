@@ -0,0 +1,67 @@
+//! nestest no modo automático (PC = $C000). O trace é conferido contra fatos conhecidos do
+//! `nestest.log` do Nintendulator e contra os códigos de resultado do próprio ROM.
+//!
+//! A comparação linha a linha (inclusive as colunas PPU e CYC) usa o log de referência
+//! do Nintendulator em `core/tests/nestest.log`, que não é gerado por este emulador.
+//! Sem o arquivo o teste avisa no stderr e só os fatos conhecidos são conferidos.
+
+use std::fs;
+use std::path::Path;
+use nes_core::Nes;
+
+const ROM: &[u8] = include_bytes!("../../nestest.nes");
+
+/// Linhas de trace do modo automático até o RTS final devolver o controle ($0001).
+fn trace() -> (Nes, Vec<String>) {
+    let mut nes = Nes::from_rom_bytes(ROM).expect("invalid nestest.nes");
+    nes.start_at(0xC000);
+
+    let mut lines = Vec::new();
+    while nes.cpu().registers.program_counter != 0x0001 && lines.len() < 10_000 {
+        lines.push(nes.trace_line());
+        nes.step_instruction();
+    }
+    (nes, lines)
+}
+
+#[test]
+fn nestest_passes() {
+    let (nes, lines) = trace();
+
+    // Códigos de erro do próprio nestest: $02 para opcodes oficiais, $03 para os não oficiais
+    assert_eq!(nes.peek(0x0002), 0x00, "official opcode test failed");
+    assert_eq!(nes.peek(0x0003), 0x00, "unofficial opcode test failed");
+
+    // Pontos conhecidos do log de referência
+    assert_eq!(lines.len(), 8991);
+    assert_eq!(
+        lines[0],
+        "C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 21 CYC:7"
+    );
+    assert!(lines[3347].starts_with("DBB5  6C FF 02  JMP ($02FF) = A900"), "{}", lines[3347]);
+    let last = lines.last().unwrap();
+    assert!(last.starts_with("C66E  60        RTS") && last.ends_with("CYC:26554"), "{}", last);
+}
+
+#[test]
+fn nestest_matches_reference_log() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/nestest.log");
+    let Ok(reference) = fs::read_to_string(&path) else {
+        eprintln!("warning: {} not found, skipping the line-by-line diff", path.display());
+        return;
+    };
+    let (_, lines) = trace();
+
+    for (index, expected) in reference.lines().enumerate() {
+        let actual = lines.get(index).map(String::as_str).unwrap_or("<end of trace>");
+        assert!(
+            actual == expected,
+            "nestest diverged at line {}\n  previous: {}\n  expected: {}\n  actual:   {}",
+            index + 1,
+            if index > 0 { lines[index - 1].as_str() } else { "" },
+            expected,
+            actual
+        );
+    }
+    assert_eq!(lines.len(), reference.lines().count(), "trace is longer than the reference");
+}
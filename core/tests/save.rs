@@ -0,0 +1,62 @@
+//! PRG-RAM com bateria: export/import do save e a flag de sujo.
+
+mod common;
+
+use common::nrom;
+use nes_core::Nes;
+
+/// LDA #$42; STA $6010; JMP *
+const WRITE_SAVE: [u8; 8] = [0xa9, 0x42, 0x8d, 0x10, 0x60, 0x4c, 0x05, 0x80];
+
+#[test]
+fn exports_battery_ram_and_flags_changes() {
+    let mut nes = Nes::from_rom_bytes(&nrom(&WRITE_SAVE, true)).unwrap();
+    assert!(nes.has_battery());
+    assert!(!nes.take_save_dirty());
+
+    nes.run_frame();
+    assert!(nes.take_save_dirty());
+    assert!(!nes.take_save_dirty());
+
+    let save = nes.save_data().unwrap();
+    assert_eq!(save.len(), 0x2000);
+    assert_eq!(save[0x10], 0x42);
+}
+
+#[test]
+fn imports_save_into_prg_ram() {
+    let mut save = vec![0; 0x2000];
+    save[0x123] = 0x99;
+
+    let mut nes = Nes::from_rom_bytes(&nrom(&[0x4c, 0x00, 0x80], true)).unwrap();
+    nes.load_save_data(&save).unwrap();
+    assert_eq!(nes.peek(0x6123), 0x99);
+    assert!(!nes.take_save_dirty());
+
+    // O save sobrevive a um power cycle
+    nes.power_on();
+    assert_eq!(nes.save_data().unwrap(), save);
+
+    assert!(nes.load_save_data(&[0; 16]).is_err());
+}
+
+#[test]
+fn no_save_without_battery() {
+    let mut nes = Nes::from_rom_bytes(&nrom(&WRITE_SAVE, false)).unwrap();
+    nes.run_frame();
+    assert!(!nes.has_battery());
+    assert!(nes.save_data().is_none());
+    assert!(!nes.take_save_dirty());
+    assert!(nes.load_save_data(&[0; 0x2000]).is_err());
+    // A work RAM continua acessível
+    assert_eq!(nes.peek(0x6010), 0x42);
+}
+
+#[test]
+fn known_board_gets_battery_without_header_flag() {
+    let zelda = include_bytes!("../../roms/Zelda.nes");
+    assert_eq!(zelda[6] & 0x02, 0, "the bundled dump lacks the battery bit");
+    let nes = Nes::from_rom_bytes(zelda).unwrap();
+    assert!(nes.has_battery());
+    assert_eq!(nes.save_data().map(|save| save.len()), Some(0x2000));
+}
@@ -0,0 +1,84 @@
+//! Protocolo $6000 das ROMs de teste, com programas NROM montados à mão.
+
+mod common;
+
+use nes_core::test_rom::{ self, TestStatus };
+use nes_core::Nes;
+
+/// STA da assinatura DE B0 61 em $6001-$6003.
+const SIGNATURE: [u8; 15] = [
+    0xa9, 0xde, 0x8d, 0x01, 0x60, // LDA #$DE; STA $6001
+    0xa9, 0xb0, 0x8d, 0x02, 0x60, // LDA #$B0; STA $6002
+    0xa9, 0x61, 0x8d, 0x03, 0x60, // LDA #$61; STA $6003
+];
+
+/// Programa em `base` que escreve a assinatura, copia `message` para $6004 e termina
+/// com `code` em $6000.
+fn report(base: u16, code: u8, message: &str) -> Vec<u8> {
+    let message_address = base + 41;
+    let hang = base + 38;
+    let mut program = vec![0xa9, 0x80, 0x8d, 0x00, 0x60]; // LDA #$80; STA $6000
+    program.extend(SIGNATURE);
+    program.extend([
+        0xa2, 0x00, // LDX #$00
+        0xbd, message_address as u8, (message_address >> 8) as u8, // loop: LDA message,X
+        0x9d, 0x04, 0x60, // STA $6004,X
+        0xf0, 0x03, // BEQ done
+        0xe8, // INX
+        0xd0, 0xf5, // BNE loop
+        0xa9, code, 0x8d, 0x00, 0x60, // done: LDA #code; STA $6000
+        0x4c, hang as u8, (hang >> 8) as u8, // hang: JMP hang
+    ]);
+    program.extend(message.as_bytes());
+    program.push(0);
+    program
+}
+
+fn run(program: &[u8], max_frames: u32) -> test_rom::TestReport {
+    let mut nes = Nes::from_rom_bytes(&common::nrom(program, false)).expect("invalid test ROM");
+    test_rom::run(&mut nes, max_frames)
+}
+
+#[test]
+fn reports_pass_and_message() {
+    let report = run(&report(0x8000, 0, "All tests passed\n"), 60);
+    assert_eq!(report.status, TestStatus::Passed);
+    assert_eq!(report.message, "All tests passed");
+}
+
+#[test]
+fn reports_failure_code() {
+    let report = run(&report(0x8000, 3, "Failed #3"), 60);
+    assert_eq!(report.status, TestStatus::Failed(3));
+    assert_eq!(report.message, "Failed #3");
+}
+
+#[test]
+fn resets_when_requested() {
+    // Primeiro boot: marca $6100 e pede reset ($81). Depois do reset, a PRG-RAM mantém a
+    // marca e o programa informa o resultado.
+    let mut program = vec![
+        0xad, 0x00, 0x61, // LDA $6100
+        0xd0, 0x1c, // BNE main
+        0xa9, 0x01, 0x8d, 0x00, 0x61, // LDA #$01; STA $6100
+    ];
+    program.extend(SIGNATURE);
+    program.extend([
+        0xa9, 0x81, 0x8d, 0x00, 0x60, // LDA #$81; STA $6000
+        0x4c, 0x1e, 0x80, // JMP *
+    ]);
+    assert_eq!(program.len(), 33);
+    program.extend(report(0x8000 + 33, 0, "Passed after reset"));
+
+    let report = run(&program, 60);
+    assert_eq!(report.status, TestStatus::Passed);
+    assert_eq!(report.message, "Passed after reset");
+}
+
+#[test]
+fn times_out_without_signature() {
+    let report = run(&[0x4c, 0x00, 0x80], 10); // JMP $8000
+    assert_eq!(report.status, TestStatus::TimedOut);
+    assert_eq!(report.frames, 10);
+    assert!(report.message.is_empty());
+}
@@ -0,0 +1,16 @@
+use nes_core::logging::{ Level, LogEntry, LogSink };
+use wasm_bindgen::JsValue;
+
+/// Encaminha o log do núcleo para o console do navegador.
+pub struct ConsoleSink;
+
+impl LogSink for ConsoleSink {
+    fn log(&mut self, entry: &LogEntry) {
+        let message = JsValue::from_str(&entry.to_string());
+        match entry.level {
+            Level::Error => web_sys::console::error_1(&message),
+            Level::Warn => web_sys::console::warn_1(&message),
+            _ => web_sys::console::log_1(&message),
+        }
+    }
+}
@@ -1,3 +0,0 @@
-include!("chip.rs");
-include!("map_instructions.rs");
-include!("instructions.rs");
\ No newline at end of file
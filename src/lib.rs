@@ -1,39 +1,48 @@
-mod cpu;
-mod rom;
-mod input;
-mod ppu;
-mod apu;
-mod mapper;
-mod bus;
-mod nes;
-
-use crate::{ cpu::CPU, ppu::PPU, rom::ROM, bus::BUS, apu::APU };
+//! Frontend wasm: adapta o `Nes` do `nes_core` ao canvas, ao áudio e ao teclado do navegador.
+
+mod console;
+
+use nes_core::{ log, logging, Nes };
+use nes_core::cpu::{ CpuVariant, IllegalOpcodePolicy };
+use nes_core::input::{ Key, KeyMap };
+use nes_core::logging::{ Category, Level };
 use wasm_bindgen::prelude::*;
 use web_sys::{ CanvasRenderingContext2d, HtmlCanvasElement };
-use crate::input::Key;
+use crate::console::ConsoleSink;
+
+/// Resultado de `run_frame`/`run_frames`/`run_cycles`.
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct FrameInfo {
+    /// Frames completados nesta chamada
+    pub frames: u32,
+    /// Ciclos de CPU executados nesta chamada
+    pub cpu_cycles: u32,
+    /// Ciclos de CPU desde o power-up (f64 para não virar BigInt no JS)
+    pub total_cycles: f64,
+}
 
 #[wasm_bindgen]
 pub struct Emulator {
-    cpu: CPU,
+    nes: Nes,
     context: CanvasRenderingContext2d,
+    keymaps: [KeyMap; 2],
 }
 
 #[wasm_bindgen]
 impl Emulator {
     #[wasm_bindgen(constructor)]
     pub fn new(canvas_id: &str, rom_data: &[u8]) -> Result<Emulator, JsValue> {
+        logging::clear_sinks();
+        logging::add_sink(Box::new(ConsoleSink));
+
         // Log ROM data size and first few bytes
-        web_sys::console::log_1(
-            &format!(
-                "Creating Emulator - ROM size: {}, First bytes: {:?}",
-                rom_data.len(),
-                &rom_data
-                    .get(0..16)
-                    .unwrap_or(&[])
-                    .iter()
-                    .map(|b| format!("{:02x}", b))
-                    .collect::<Vec<_>>()
-            ).into()
+        log!(
+            Category::Frontend,
+            Level::Info,
+            "Creating Emulator - ROM size: {}, First bytes: {:02x?}",
+            rom_data.len(),
+            rom_data.get(0..16).unwrap_or(&[])
         );
 
         // Get canvas and context with error handling
@@ -57,64 +66,193 @@ impl Emulator {
         context.set_image_smoothing_enabled(false);
 
         // Initialize components with detailed error handling
-        let mut ppu = PPU::new();
-
-        web_sys::console::log_1(&"Creating ROM...".into());
-        let rom = ROM::from_bytes(rom_data).map_err(|e| {
-            web_sys::console::error_1(&format!("ROM creation failed: {:?}", e).into());
+        log!(Category::Frontend, Level::Debug, "Creating ROM...");
+        let nes = Nes::from_rom_bytes(rom_data).map_err(|e| {
+            log!(Category::Rom, Level::Error, "ROM creation failed: {:?}", e);
             JsValue::from_str(&format!("ROM error: {}", e))
         })?;
 
-        // Configure PPU mirroring from ROM
-        let mirroring = rom.get_mirroring();
-        ppu.set_mirroring(mirroring);
-        web_sys::console::log_1(&format!("PPU Mirroring set to: {:?}", mirroring).into());
+        log!(Category::Frontend, Level::Debug, "Emulator creation completed");
+        Ok(Emulator {
+            nes,
+            context,
+            keymaps: [KeyMap::player_one(), KeyMap::player_two()],
+        })
+    }
 
-        let chr_data = rom.mapper.get_chr_rom(); // Você precisará implementar este método em sua struct ROM
-        web_sys::console::log_1(&format!("CHR data size: {}", chr_data.len()).into());
+    /// Executa um único ciclo de CPU (3 de PPU). Útil para depuração; para rodar em
+    /// tempo real use `run_frame` ou `run_cycles`.
+    pub fn tick(&mut self) -> Result<(), JsValue> {
+        self.check_halted()?;
 
+        let frame_complete = self.nes.tick();
 
-        web_sys::console::log_1(&format!("CHR: {:?}", chr_data).into());
-        ppu.load_chr_data(&chr_data);
-        web_sys::console::log_1(&"CHR data loaded into PPU".into());
+        if frame_complete {
+            self.render().unwrap_or_else(|e| {
+                log!(Category::Frontend, Level::Error, "Render error: {:?}", e);
+            });
+        }
+        Ok(())
+    }
 
-        web_sys::console::log_1(&"Creating APU...".into());
-        let apu = APU::new();
+    /// Executa até a PPU completar um frame e desenha o frame no canvas.
+    #[wasm_bindgen]
+    pub fn run_frame(&mut self) -> Result<FrameInfo, JsValue> {
+        self.run_frames(1)
+    }
 
-        web_sys::console::log_1(&"Creating BUS...".into());
-        let mut bus = BUS::new(ppu, rom, apu);
+    /// Executa `count` frames completos; só o último é desenhado.
+    #[wasm_bindgen]
+    pub fn run_frames(&mut self, count: u32) -> Result<FrameInfo, JsValue> {
+        let start = self.nes.cycles();
+        let mut frames = 0;
+        while frames < count {
+            // run_frame só volta sem completar o frame se a CPU parou
+            if !self.nes.run_frame() {
+                self.check_halted()?;
+            }
+            frames += 1;
+        }
+        self.finish_run(start, frames)
+    }
 
-        bus.ppu.set_mirroring(bus.rom.get_mirroring());
+    /// Executa até `budget` ciclos de CPU (ex.: tempo decorrido × 1.789.773 Hz), desenhando
+    /// o último frame completado, se houver. Permite ao loop JS manter a velocidade real.
+    #[wasm_bindgen]
+    pub fn run_cycles(&mut self, budget: u32) -> Result<FrameInfo, JsValue> {
+        let start = self.nes.cycles();
+        let mut frames = 0;
+        for _ in 0..budget {
+            self.check_halted()?;
+            if self.nes.tick() {
+                frames += 1;
+            }
+        }
+        self.finish_run(start, frames)
+    }
 
-        web_sys::console::log_1(&"Finish BUS...".into());
+    fn check_halted(&self) -> Result<(), JsValue> {
+        match self.nes.halted() {
+            Some((address, opcode)) =>
+                Err(
+                    JsValue::from_str(
+                        &format!("CPU halted on opcode {:02X} at {:04X}", opcode, address)
+                    )
+                ),
+            None => Ok(()),
+        }
+    }
 
-        web_sys::console::log_1(&"Creating CPU...".into());
-        let mut cpu = CPU::new(bus);
+    fn finish_run(&self, start_cycles: u64, frames: u32) -> Result<FrameInfo, JsValue> {
+        if frames > 0 {
+            self.render()?;
+        }
+        Ok(FrameInfo {
+            frames,
+            cpu_cycles: (self.nes.cycles() - start_cycles) as u32,
+            total_cycles: self.nes.cycles() as f64,
+        })
+    }
 
-        web_sys::console::log_1(&"Resetting CPU...".into());
-        cpu.reset();
+    /// Botão de reset do console: RAM e registros A, X e Y são preservados.
+    #[wasm_bindgen]
+    pub fn reset(&mut self) {
+        self.nes.reset();
+    }
 
-        web_sys::console::log_1(&"Emulator creation completed".into());
-        Ok(Emulator {
-            cpu,
-            context,
-        })
+    /// Desliga e liga o console (RAM, PPU e APU voltam ao estado de power-up).
+    #[wasm_bindgen]
+    pub fn power_cycle(&mut self) {
+        self.nes.power_on();
     }
 
-    pub fn tick(&mut self) {
-        let frame_complete = self.cpu.clock();
+    /// Se o cartucho tem save (PRG-RAM com bateria).
+    #[wasm_bindgen]
+    pub fn has_battery(&self) -> bool {
+        self.nes.has_battery()
+    }
 
-        if frame_complete {
-            self.render().unwrap_or_else(|e| {
-                web_sys::console::error_1(&format!("Render error: {:?}", e).into());
-            });
+    /// Bytes do save para guardar no IndexedDB; `undefined` se o cartucho não tem bateria.
+    #[wasm_bindgen]
+    pub fn export_save(&self) -> Option<Vec<u8>> {
+        self.nes.save_data()
+    }
+
+    /// Restaura um save exportado com `export_save`.
+    #[wasm_bindgen]
+    pub fn import_save(&mut self, data: &[u8]) -> Result<(), JsValue> {
+        self.nes
+            .load_save_data(data)
+            .map_err(|e| JsValue::from_str(&format!("Save error: {}", e)))
+    }
+
+    /// `true` (uma vez) quando o save mudou desde a última chamada: hora de chamar
+    /// `export_save` e gravar.
+    #[wasm_bindgen]
+    pub fn take_save_dirty(&mut self) -> bool {
+        self.nes.take_save_dirty()
+    }
+
+    /// Variante da CPU: "2A03" (padrão do NES), "6502" ou "65C02".
+    #[wasm_bindgen]
+    pub fn set_cpu_variant(&mut self, variant: &str) -> Result<(), JsValue> {
+        self.nes.cpu_mut().variant = CpuVariant::from_name(variant).ok_or_else(||
+            JsValue::from_str(&format!("Invalid CPU variant: {}", variant))
+        )?;
+        Ok(())
+    }
+
+    /// Nível de log de uma categoria ("cpu", "ppu", "apu", "mapper", "rom", "frontend" ou
+    /// "all"); `level` é "error", "warn", "info", "debug", "trace" ou "off".
+    #[wasm_bindgen]
+    pub fn set_log_level(&mut self, category: &str, level: &str) -> Result<(), JsValue> {
+        let level = match level {
+            "off" => None,
+            _ =>
+                Some(
+                    Level::from_name(level).ok_or_else(||
+                        JsValue::from_str(&format!("Invalid log level: {}", level))
+                    )?
+                ),
+        };
+        if category == "all" {
+            logging::set_all_levels(level);
+        } else {
+            let category = Category::from_name(category).ok_or_else(||
+                JsValue::from_str(&format!("Invalid log category: {}", category))
+            )?;
+            logging::set_level(category, level);
         }
+        Ok(())
+    }
+
+    /// Retira as mensagens acumuladas no ring buffer de log (para o painel de debug).
+    #[wasm_bindgen]
+    pub fn take_log_entries(&mut self) -> js_sys::Array {
+        logging::take_ring_buffer()
+            .iter()
+            .map(|entry| JsValue::from_str(&entry.to_string()))
+            .collect()
+    }
+
+    /// Política para opcodes instáveis e JAM: "emulate", "halt" ou "log".
+    #[wasm_bindgen]
+    pub fn set_illegal_opcode_policy(&mut self, policy: &str) -> Result<(), JsValue> {
+        self.nes.cpu_mut().illegal_opcode_policy = match policy {
+            "emulate" => IllegalOpcodePolicy::Emulate,
+            "halt" => IllegalOpcodePolicy::Halt,
+            "log" => IllegalOpcodePolicy::Log,
+            _ => {
+                return Err(JsValue::from_str(&format!("Invalid policy: {}", policy)));
+            }
+        };
+        Ok(())
     }
 
     fn render(&self) -> Result<(), JsValue> {
-        let framebuffer = self.cpu.bus.ppu.get_framebuffer();
+        let framebuffer = self.nes.framebuffer();
         
-        web_sys::console::log_1(&format!("Rendering frame, buffer size: {}", framebuffer.len()).into());
+        log!(Category::Frontend, Level::Trace, "Rendering frame, buffer size: {}", framebuffer.len());
         
         let mut rgba_buffer = vec![0; 256 * 240 * 4];
         
@@ -141,12 +279,25 @@ impl Emulator {
         Ok(())
     }
 
+    /// Define a taxa de amostragem do áudio (normalmente `AudioContext.sampleRate`).
+    #[wasm_bindgen]
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.nes.set_sample_rate(sample_rate);
+    }
+
+    /// Entrega ao JS as amostras de áudio geradas desde a última chamada.
+    #[wasm_bindgen]
+    pub fn audio_samples(&mut self) -> js_sys::Float32Array {
+        let samples = self.nes.take_audio_samples();
+        js_sys::Float32Array::from(&samples[..])
+    }
+
     #[wasm_bindgen]
     pub fn get_registers_cpu(&self) -> JsValue {
         let regs = js_sys::Object::new();
 
         let (acc, index_x, index_y, program_counter, stack_pointer, status_register) =
-            self.cpu.get_all_registers();
+            self.nes.cpu().get_all_registers();
 
         js_sys::Reflect
             ::set(&regs, &JsValue::from_str("A"), &JsValue::from_f64(acc as f64))
@@ -174,7 +325,7 @@ impl Emulator {
         let regs = js_sys::Object::new();
 
         let (ctrl, mask, status, oam_addr, scroll, addr_low, addr_full) =
-            self.cpu.bus.ppu.get_all_registers();
+            self.nes.ppu().get_all_registers();
 
         // PPU Registers
         js_sys::Reflect
@@ -208,31 +359,73 @@ impl Emulator {
         regs.into()
     }
 
+    /// Pressiona o botão mapeado para `key` no keymap de qualquer jogador.
+    /// Retorna `true` se a tecla estava mapeada.
+    #[wasm_bindgen]
+    pub fn key_down(&mut self, key: &str) -> bool {
+        self.update_key(None, key, true)
+    }
+
+    #[wasm_bindgen]
+    pub fn key_up(&mut self, key: &str) -> bool {
+        self.update_key(None, key, false)
+    }
+
+    /// Como `key_down`, mas considerando apenas o keymap do jogador `player` (0 ou 1).
+    #[wasm_bindgen]
+    pub fn key_down_player(&mut self, player: usize, key: &str) -> bool {
+        self.update_key(Some(player), key, true)
+    }
+
     #[wasm_bindgen]
-    pub fn key_down(&mut self, key: &str) {
-        if let Some(key) = map_key(key) {
-            self.cpu.bus.controller.update(key, true);
+    pub fn key_up_player(&mut self, player: usize, key: &str) -> bool {
+        self.update_key(Some(player), key, false)
+    }
+
+    /// Associa a tecla `key` ao botão `button` ("A", "B", "Select", "Start", "Up", ...)
+    /// do jogador `player`.
+    #[wasm_bindgen]
+    pub fn set_key_binding(
+        &mut self,
+        player: usize,
+        key: &str,
+        button: &str
+    ) -> Result<(), JsValue> {
+        let keymap = self.keymaps
+            .get_mut(player)
+            .ok_or_else(|| JsValue::from_str(&format!("Invalid player: {}", player)))?;
+        let button = Key::from_name(button).ok_or_else(||
+            JsValue::from_str(&format!("Invalid button: {}", button))
+        )?;
+        keymap.bind(key, button);
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub fn remove_key_binding(&mut self, player: usize, key: &str) {
+        if let Some(keymap) = self.keymaps.get_mut(player) {
+            keymap.unbind(key);
         }
     }
 
     #[wasm_bindgen]
-    pub fn key_up(&mut self, key: &str) {
-        if let Some(key) = map_key(key) {
-            self.cpu.bus.controller.update(key, false);
+    pub fn clear_key_bindings(&mut self, player: usize) {
+        if let Some(keymap) = self.keymaps.get_mut(player) {
+            keymap.clear();
         }
     }
-}
 
-fn map_key(key: &str) -> Option<Key> {
-    match key {
-        "z" => Some(Key::A),
-        "x" => Some(Key::B),
-        "Control" => Some(Key::Select),
-        "Enter" => Some(Key::Start),
-        "ArrowUp" => Some(Key::Up),
-        "ArrowDown" => Some(Key::Down),
-        "ArrowLeft" => Some(Key::Left),
-        "ArrowRight" => Some(Key::Right),
-        _ => None,
+    fn update_key(&mut self, player: Option<usize>, key: &str, pressed: bool) -> bool {
+        let mut handled = false;
+        for (index, keymap) in self.keymaps.iter().enumerate() {
+            if player.is_some_and(|p| p != index) {
+                continue;
+            }
+            if let Some(button) = keymap.get(key) {
+                self.nes.set_button(index, button, pressed);
+                handled = true;
+            }
+        }
+        handled
     }
 }
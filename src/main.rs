@@ -1,41 +1,362 @@
-use apu::APU;
-use cpu::CPU;
-use std::time::{Duration, Instant};
-
-mod mapper;
-mod cpu;
-mod rom;
-mod input;
-mod ppu;
-mod apu;
-mod bus;
-
-fn main() {
-    let rom_path = "nestest.nes";
-
-    let rom = match rom::ROM::new(rom_path) {
-        Ok(rom) => rom,
-        Err(e) => panic!("Error loading ROM: {}", e),
+//! Runner nativo sem interface: carrega uma ROM, roda um número de frames ou até uma
+//! condição e grava snapshots, para scripts de regressão sem navegador.
+
+mod snapshot;
+
+use nes_core::logging::{ self, Category, Level };
+use nes_core::test_rom::{ self, TestStatus };
+use nes_core::Nes;
+use std::fs;
+use std::io::{ self, BufWriter, Write };
+use std::path::{ Path, PathBuf };
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Uso: web_nes <rom.nes> [opções]
+       web_nes --test <rom.nes | diretório> [--frames N]
+
+Opções:
+  --frames N            roda N frames (padrão 60); com --until-* ou --test, é o
+                        limite (padrão 3600 no --test)
+  --start-pc ADDR       começa em ADDR em vez do vetor de reset (nestest: C000)
+  --until-pc ADDR       para quando o PC chegar em ADDR (hex)
+  --until-mem ADDR=VAL  para quando o byte em ADDR valer VAL (hex)
+  --screenshot ARQ      grava o último frame (.png ou .ppm)
+  --dump-ram ARQ        grava os 2 KB de RAM (\"-\" imprime um hexdump)
+  --save ARQ            carrega o save (.sav) de ARQ, se existir, e grava a PRG-RAM
+                        com bateria nele no final
+  --trace               imprime uma linha de trace por instrução
+  --log CAT=NIVEL       habilita o log de uma categoria (ou \"all\")
+  --test                roda ROMs de teste que informam o resultado em $6000
+                        (blargg); com um diretório, roda todas as .nes dele
+  -h, --help            mostra esta ajuda
+
+Códigos de saída:
+  0  terminou (frames executados ou condição atingida)
+  1  erro de uso, de ROM ou de E/S
+  2  a CPU parou (JAM ou política de opcode ilegal)
+  3  o limite de frames acabou antes da condição
+  4  algum teste do --test falhou";
+
+const EXIT_ERROR: u8 = 1;
+const EXIT_HALTED: u8 = 2;
+const EXIT_TIMEOUT: u8 = 3;
+const EXIT_FAILED: u8 = 4;
+
+const DEFAULT_FRAMES: u32 = 60;
+const DEFAULT_TEST_FRAMES: u32 = 60 * 60;
+
+struct Options {
+    rom_path: PathBuf,
+    frames: Option<u32>,
+    start_pc: Option<u16>,
+    until_pc: Option<u16>,
+    until_mem: Option<(u16, u8)>,
+    screenshot: Option<PathBuf>,
+    dump_ram: Option<PathBuf>,
+    save: Option<PathBuf>,
+    trace: bool,
+    test: bool,
+}
+
+enum Outcome {
+    Finished,
+    ConditionMet,
+    Halted(u16, u8),
+    Timeout,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(EXIT_ERROR);
+        }
     };
-    
-    let ppu = ppu::PPU::new();
-    let apu = APU::new();
-    let bus = bus::BUS::new(ppu, rom, apu);
 
-    let mut cpu: CPU = CPU::new(bus);
-    cpu.reset();
+    if options.test {
+        return match run_tests(&options) {
+            Ok(code) => ExitCode::from(code),
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::from(EXIT_ERROR)
+            }
+        };
+    }
 
-    let frame_time = Duration::from_nanos(16_666_667);
-    let mut last_frame = Instant::now();
+    match run(&options) {
+        Ok(Outcome::Finished | Outcome::ConditionMet) => ExitCode::SUCCESS,
+        Ok(Outcome::Halted(address, opcode)) => {
+            eprintln!("CPU halted on opcode {:02X} at {:04X}", opcode, address);
+            ExitCode::from(EXIT_HALTED)
+        }
+        Ok(Outcome::Timeout) => {
+            eprintln!(
+                "Condition not reached after {} frames",
+                options.frames.unwrap_or(DEFAULT_FRAMES)
+            );
+            ExitCode::from(EXIT_TIMEOUT)
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn load(path: &Path) -> Result<Nes, String> {
+    let contents = fs::read(path).map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+    Nes::from_rom_bytes(&contents).map_err(|e| format!("Error loading {}: {}", path.display(), e))
+}
+
+fn run(options: &Options) -> Result<Outcome, String> {
+    let mut nes = load(&options.rom_path)?;
+    let max_frames = options.frames.unwrap_or(DEFAULT_FRAMES);
+    if let Some(path) = &options.save {
+        if !nes.has_battery() {
+            return Err(format!("{} has no battery-backed RAM", options.rom_path.display()));
+        }
+        if path.exists() {
+            let data = fs::read(path).map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+            nes.load_save_data(&data).map_err(|e|
+                format!("Error loading {}: {}", path.display(), e)
+            )?;
+        }
+    }
+    if let Some(pc) = options.start_pc {
+        nes.start_at(pc);
+    }
 
-    loop {
-        cpu.clock();
+    let has_condition = options.until_pc.is_some() || options.until_mem.is_some();
+    let mut trace = BufWriter::new(io::stdout().lock());
+    let mut frames = 0;
+    let outcome = loop {
+        if
+            options.until_pc.is_some_and(|pc| nes.cpu().registers.program_counter == pc) ||
+            options.until_mem.is_some_and(|(address, value)| nes.peek(address) == value)
+        {
+            break Outcome::ConditionMet;
+        }
+        if let Some((address, opcode)) = nes.halted() {
+            break Outcome::Halted(address, opcode);
+        }
+        if frames >= max_frames {
+            break if has_condition { Outcome::Timeout } else { Outcome::Finished };
+        }
 
-        // Frame timing
-        let elapsed = last_frame.elapsed();
-        if elapsed < frame_time {
-            std::thread::sleep(frame_time - elapsed);
+        // Logo após o power-up a CPU ainda está na sequência de reset
+        if options.trace && nes.cpu().remaining_cycles == 0 {
+            writeln!(trace, "{}", nes.trace_line()).map_err(|e| format!("Error writing trace: {}", e))?;
+        }
+        if nes.step_instruction() {
+            frames += 1;
         }
-        last_frame = Instant::now();
+    };
+    trace.flush().map_err(|e| format!("Error writing trace: {}", e))?;
+
+    eprintln!(
+        "{} frames, {} CPU cycles, PC={:04X}",
+        frames,
+        nes.cycles(),
+        nes.cpu().registers.program_counter
+    );
+
+    if let Some(path) = &options.screenshot {
+        snapshot::write_image(path, nes.framebuffer()).map_err(|e|
+            format!("Error writing {}: {}", path.display(), e)
+        )?;
+    }
+    if let Some(path) = &options.dump_ram {
+        if path.as_os_str() == "-" {
+            print_hexdump(nes.ram());
+        } else {
+            fs::write(path, nes.ram()).map_err(|e|
+                format!("Error writing {}: {}", path.display(), e)
+            )?;
+        }
+    }
+    if let (Some(path), Some(data)) = (&options.save, nes.save_data()) {
+        fs::write(path, data).map_err(|e| format!("Error writing {}: {}", path.display(), e))?;
+    }
+
+    Ok(outcome)
+}
+
+/// Roda uma ROM de teste, ou todas as `.nes` de um diretório, e imprime o resultado de
+/// cada uma. Retorna o código de saída.
+fn run_tests(options: &Options) -> Result<u8, String> {
+    let max_frames = options.frames.unwrap_or(DEFAULT_TEST_FRAMES);
+    let paths = if options.rom_path.is_dir() {
+        let mut paths: Vec<PathBuf> = fs
+            ::read_dir(&options.rom_path)
+            .map_err(|e| format!("Error reading {}: {}", options.rom_path.display(), e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("nes")))
+            .collect();
+        paths.sort();
+        paths
+    } else {
+        vec![options.rom_path.clone()]
+    };
+
+    let mut exit_code = 0;
+    let mut passed = 0;
+    for path in &paths {
+        let report = match load(path) {
+            Ok(mut nes) => test_rom::run(&mut nes, max_frames),
+            Err(e) => {
+                println!("ERROR {}", e);
+                exit_code = EXIT_ERROR;
+                continue;
+            }
+        };
+
+        let (label, code) = match report.status {
+            TestStatus::Passed => ("PASS", 0),
+            TestStatus::Failed(_) => ("FAIL", EXIT_FAILED),
+            TestStatus::TimedOut => ("TIMEOUT", EXIT_TIMEOUT),
+            TestStatus::Halted(..) => ("HALT", EXIT_HALTED),
+        };
+        let detail = match report.status {
+            TestStatus::Failed(code) => format!(" (code {})", code),
+            TestStatus::Halted(address, opcode) =>
+                format!(" (opcode {:02X} at {:04X})", opcode, address),
+            _ => String::new(),
+        };
+        println!("{:<7} {}{} [{} frames]", label, path.display(), detail, report.frames);
+        for line in report.message.lines() {
+            println!("        {}", line);
+        }
+
+        if report.passed() {
+            passed += 1;
+        } else if exit_code == 0 {
+            // Um único arquivo mantém o código específico; um diretório usa EXIT_FAILED
+            exit_code = if paths.len() == 1 { code } else { EXIT_FAILED };
+        }
+    }
+
+    if paths.len() > 1 {
+        println!("{}/{} passed", passed, paths.len());
+    }
+    Ok(exit_code)
+}
+
+/// Retorna `None` quando só foi pedida a ajuda.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut rom_path = None;
+    let mut options = Options {
+        rom_path: PathBuf::new(),
+        frames: None,
+        start_pc: None,
+        until_pc: None,
+        until_mem: None,
+        screenshot: None,
+        dump_ram: None,
+        save: None,
+        trace: false,
+        test: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("Missing value for {}", name));
+        match arg.as_str() {
+            "-h" | "--help" => {
+                return Ok(None);
+            }
+            "--frames" => {
+                let frames = value(&arg)?;
+                options.frames = Some(
+                    frames.parse().map_err(|_| format!("Invalid frame count: {}", frames))?
+                );
+            }
+            "--start-pc" => {
+                options.start_pc = Some(parse_hex(&value(&arg)?)?);
+            }
+            "--until-pc" => {
+                options.until_pc = Some(parse_hex(&value(&arg)?)?);
+            }
+            "--until-mem" => {
+                let condition = value(&arg)?;
+                let (address, byte) = condition
+                    .split_once('=')
+                    .ok_or_else(|| format!("Expected ADDR=VAL: {}", condition))?;
+                let byte = parse_hex(byte)?;
+                let byte = u8::try_from(byte).map_err(|_| format!("Invalid byte: {:X}", byte))?;
+                options.until_mem = Some((parse_hex(address)?, byte));
+            }
+            "--screenshot" => {
+                options.screenshot = Some(PathBuf::from(value(&arg)?));
+            }
+            "--dump-ram" => {
+                options.dump_ram = Some(PathBuf::from(value(&arg)?));
+            }
+            "--save" => {
+                options.save = Some(PathBuf::from(value(&arg)?));
+            }
+            "--trace" => {
+                options.trace = true;
+            }
+            "--test" => {
+                options.test = true;
+            }
+            "--log" => {
+                let setting = value(&arg)?;
+                set_log_level(&setting)?;
+            }
+            _ if arg.starts_with('-') => {
+                return Err(format!("Unknown option: {}", arg));
+            }
+            _ if rom_path.is_none() => {
+                rom_path = Some(PathBuf::from(arg));
+            }
+            _ => {
+                return Err(format!("Unexpected argument: {}", arg));
+            }
+        }
+    }
+
+    options.rom_path = rom_path.ok_or("Missing ROM path")?;
+    Ok(Some(options))
+}
+
+fn parse_hex(text: &str) -> Result<u16, String> {
+    let digits = text.trim_start_matches('$').trim_start_matches("0x");
+    u16::from_str_radix(digits, 16).map_err(|_| format!("Invalid hex value: {}", text))
+}
+
+/// `cpu=trace`, `all=warn`, `mapper=off`...
+fn set_log_level(setting: &str) -> Result<(), String> {
+    let (category, level) = setting
+        .split_once('=')
+        .ok_or_else(|| format!("Expected CAT=LEVEL: {}", setting))?;
+    let level = match level {
+        "off" => None,
+        _ => Some(Level::from_name(level).ok_or_else(|| format!("Invalid log level: {}", level))?),
+    };
+    if category == "all" {
+        logging::set_all_levels(level);
+    } else {
+        let category = Category::from_name(category).ok_or_else(||
+            format!("Invalid log category: {}", category)
+        )?;
+        logging::set_level(category, level);
+    }
+    Ok(())
+}
+
+fn print_hexdump(data: &[u8]) {
+    for (row, chunk) in data.chunks(16).enumerate() {
+        let bytes = chunk
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!("{:04X}: {}", row * 16, bytes);
     }
 }
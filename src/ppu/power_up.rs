@@ -1,7 +0,0 @@
-use super::PPU;
-
-impl PPU {
-    pub fn power_up() -> Self {
-        PPU::new()
-    }
-}
@@ -0,0 +1,89 @@
+//! Gravação do framebuffer em PPM (P6) ou PNG, sem dependências externas.
+
+use std::fs;
+use std::io::{ Error, ErrorKind };
+use std::path::Path;
+use nes_core::rom::crc32;
+
+pub const WIDTH: u32 = 256;
+pub const HEIGHT: u32 = 240;
+
+/// Grava o framebuffer RGB no formato indicado pela extensão (`.png` ou `.ppm`).
+pub fn write_image(path: &Path, rgb: &[u8]) -> Result<(), Error> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    let data = match extension.as_deref() {
+        Some("png") => encode_png(rgb, WIDTH, HEIGHT),
+        Some("ppm") => encode_ppm(rgb, WIDTH, HEIGHT),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unsupported image format: {}", path.display())
+            ));
+        }
+    };
+    fs::write(path, data)
+}
+
+pub fn encode_ppm(rgb: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut data = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    data.extend_from_slice(rgb);
+    data
+}
+
+/// PNG RGB 8 bits. O IDAT usa blocos deflate "stored" (sem compressão): o arquivo fica
+/// maior, mas dispensa um compressor.
+pub fn encode_png(rgb: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let row_len = (width * 3) as usize;
+
+    // Cada linha começa com o tipo de filtro (0 = nenhum)
+    let mut raw = Vec::with_capacity((row_len + 1) * (height as usize));
+    for row in rgb.chunks(row_len).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    // zlib: cabeçalho, blocos stored de até 65535 bytes e Adler-32
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(0xFFFF).peekable();
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        zlib.push(last as u8);
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]); // 8 bits, RGB, deflate, filtro 0, sem interlace
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &ihdr);
+    write_chunk(&mut png, b"IDAT", &zlib);
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,44 @@
+// AudioWorklet que toca as amostras geradas pela APU do emulador.
+// O main thread envia um Float32Array por frame via `port.postMessage`.
+const BUFFER_SIZE = 16384;
+
+class NesAudioProcessor extends AudioWorkletProcessor {
+    constructor() {
+        super();
+        this.buffer = new Float32Array(BUFFER_SIZE);
+        this.readIndex = 0;
+        this.writeIndex = 0;
+        this.available = 0;
+        this.lastSample = 0;
+
+        this.port.onmessage = (event) => {
+            const samples = event.data;
+            for (let i = 0; i < samples.length; i++) {
+                if (this.available === BUFFER_SIZE) {
+                    // Buffer cheio: descarta a amostra mais antiga
+                    this.readIndex = (this.readIndex + 1) % BUFFER_SIZE;
+                    this.available--;
+                }
+                this.buffer[this.writeIndex] = samples[i];
+                this.writeIndex = (this.writeIndex + 1) % BUFFER_SIZE;
+                this.available++;
+            }
+        };
+    }
+
+    process(inputs, outputs) {
+        const channel = outputs[0][0];
+        for (let i = 0; i < channel.length; i++) {
+            if (this.available > 0) {
+                this.lastSample = this.buffer[this.readIndex];
+                this.readIndex = (this.readIndex + 1) % BUFFER_SIZE;
+                this.available--;
+            }
+            // Em underrun repete a última amostra para evitar estalos
+            channel[i] = this.lastSample;
+        }
+        return true;
+    }
+}
+
+registerProcessor('nes-audio-processor', NesAudioProcessor);
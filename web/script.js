@@ -3,18 +3,20 @@ import init, { Emulator } from './pkg/web_nes.js';
 let emu = null;
 let frameCount = 0;
 let isRunning = false;
+let audioContext = null;
+let audioNode = null;
+let lastTimestamp = null;
+let saveKey = null;
+let lastSaveCheck = 0;
 
-// Keyboard mapping
-const keyMap = {
-    'z': 'A',
-    'x': 'B',
-    'Control': 'Select',
-    'Enter': 'Start',
-    'ArrowUp': 'Up',
-    'ArrowDown': 'Down',
-    'ArrowLeft': 'Left',
-    'ArrowRight': 'Right'
-};
+// Clock da CPU NTSC; o loop converte o tempo real decorrido em ciclos de CPU
+const CPU_CLOCK_HZ = 1789773;
+// Evita uma rajada de emulação depois que a aba fica em segundo plano
+const MAX_FRAME_MS = 100;
+// Intervalo mínimo entre gravações do save (os jogos escrevem na PRG-RAM o tempo todo)
+const SAVE_INTERVAL_MS = 1000;
+const SAVE_DB = 'web_nes';
+const SAVE_STORE = 'saves';
 
 async function loadWasm() {
     try {
@@ -27,6 +29,74 @@ async function loadWasm() {
     }
 }
 
+async function initAudio() {
+    if (audioContext) {
+        await audioContext.resume();
+        return;
+    }
+    try {
+        audioContext = new AudioContext();
+        await audioContext.audioWorklet.addModule('web/audio-processor.js');
+        audioNode = new AudioWorkletNode(audioContext, 'nes-audio-processor', {
+            outputChannelCount: [1]
+        });
+        audioNode.connect(audioContext.destination);
+        await audioContext.resume();
+    } catch (e) {
+        console.error("Failed to initialize audio:", e);
+        audioContext = null;
+        audioNode = null;
+    }
+}
+
+function pushAudio() {
+    const samples = emu.audio_samples();
+    if (audioNode && samples.length > 0) {
+        audioNode.port.postMessage(samples, [samples.buffer]);
+    }
+}
+
+// Saves (PRG-RAM com bateria) ficam no IndexedDB, indexados pelo nome da ROM
+function openSaveDb() {
+    return new Promise((resolve, reject) => {
+        const request = indexedDB.open(SAVE_DB, 1);
+        request.onupgradeneeded = () => request.result.createObjectStore(SAVE_STORE);
+        request.onsuccess = () => resolve(request.result);
+        request.onerror = () => reject(request.error);
+    });
+}
+
+async function saveDbRequest(mode, action) {
+    const db = await openSaveDb();
+    return new Promise((resolve, reject) => {
+        const request = action(db.transaction(SAVE_STORE, mode).objectStore(SAVE_STORE));
+        request.onsuccess = () => resolve(request.result);
+        request.onerror = () => reject(request.error);
+    });
+}
+
+async function loadSave() {
+    if (!emu.has_battery()) return;
+    try {
+        const data = await saveDbRequest('readonly', store => store.get(saveKey));
+        if (data) {
+            emu.import_save(data);
+            console.log(`Save loaded: ${saveKey}`);
+        }
+    } catch (e) {
+        console.error("Failed to load save:", e);
+    }
+}
+
+function flushSave(timestamp) {
+    if (timestamp - lastSaveCheck < SAVE_INTERVAL_MS) return;
+    lastSaveCheck = timestamp;
+    if (!emu.take_save_dirty()) return;
+    const data = emu.export_save();
+    saveDbRequest('readwrite', store => store.put(data, saveKey))
+        .catch(e => console.error("Failed to write save:", e));
+}
+
 async function loadROM(file) {
     try {
         const arrayBuffer = await file.arrayBuffer();
@@ -62,12 +132,20 @@ async function loadROM(file) {
         try {
             emu = new Emulator("nes-screen", romData);
             console.log("Emulator instance created successfully:", emu);
+            saveKey = file.name;
+            await loadSave();
+            // O carregamento da ROM vem de um gesto do usuário, então o áudio pode ser iniciado
+            await initAudio();
+            if (audioContext) {
+                emu.set_sample_rate(audioContext.sampleRate);
+            }
         } catch (e) {
             console.error("Failed to create emulator:", e);
             throw e;
         }
 
         isRunning = true;
+        lastTimestamp = null;
         requestAnimationFrame(gameLoop);
         updateDebug("ROM loaded successfully");
         setupControls();
@@ -80,16 +158,22 @@ async function loadROM(file) {
     }
 }
 
-function gameLoop() {
+function gameLoop(timestamp) {
     if (!isRunning || !emu) return;
 
     try {
-        // Execute one frame
-        emu.tick();
+        // Roda os ciclos de CPU equivalentes ao tempo real desde o último callback
+        const elapsed = lastTimestamp === null
+            ? 1000 / 60
+            : Math.min(timestamp - lastTimestamp, MAX_FRAME_MS);
+        lastTimestamp = timestamp;
+        const info = emu.run_cycles(Math.round(elapsed * CPU_CLOCK_HZ / 1000));
+        pushAudio();
+        flushSave(timestamp);
         
         // Update debug info
         updateRegisters();
-        frameCount++;
+        frameCount += info.frames;
         
         // Update status
         const canvas = document.getElementById("nes-screen");
@@ -154,19 +238,16 @@ function setupControls() {
     document.addEventListener('keyup', handleKeyUp);
 }
 
+// O mapeamento de teclas dos dois jogadores fica no emulador (ver set_key_binding)
 function handleKeyDown(event) {
-    const nesKey = keyMap[event.key];
-    if (nesKey && emu) {
+    if (emu && emu.key_down(event.key)) {
         event.preventDefault();
-        emu.key_down(nesKey); // Usar o valor mapeado, não a tecla original
     }
 }
 
 function handleKeyUp(event) {
-    const nesKey = keyMap[event.key];
-    if (nesKey && emu) {
+    if (emu && emu.key_up(event.key)) {
         event.preventDefault();
-        emu.key_up(nesKey); // Usar o valor mapeado, não a tecla original
     }
 }
 